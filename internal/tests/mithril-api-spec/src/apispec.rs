@@ -109,21 +109,24 @@ impl<'a> APISpec<'a> {
         let fake_base_url = "http://0.0.0.1";
         let url = Url::parse(&format!("{fake_base_url}{path}")).unwrap();
 
-        check_query_parameter_limitations(&url, operation_object);
-
-        let mut query_pairs = url.query_pairs();
-        if let Some(parameter) = query_pairs.next() {
-            let spec_parameter = &operation_object["parameters"][0];
-            let spec_parameter_name = &spec_parameter["name"].as_str().unwrap();
-            let spec_parameter_in = &spec_parameter["in"].as_str().unwrap();
-            if spec_parameter_in.eq(&"query") && spec_parameter_name.eq(&parameter.0) {
-                Ok(self)
-            } else {
-                Err(format!("Unexpected query parameter '{}'", parameter.0))
+        let spec_query_parameters = operation_object["parameters"]
+            .as_array()
+            .map(|parameters| {
+                parameters
+                    .iter()
+                    .filter(|p| p["in"].eq("query"))
+                    .filter_map(|p| p["name"].as_str())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        for (parameter_name, _) in url.query_pairs() {
+            if !spec_query_parameters.contains(&parameter_name.as_ref()) {
+                return Err(format!("Unexpected query parameter '{parameter_name}'"));
             }
-        } else {
-            Ok(self)
         }
+
+        Ok(self)
     }
 
     /// Validates if the status is the expected one
@@ -315,25 +318,6 @@ impl<'a> APISpec<'a> {
     }
 }
 
-// TODO: For now, it verifies only one parameter,
-// should verify with multiple query parameters using an openapi.yaml file for test.
-fn check_query_parameter_limitations(url: &Url, operation_object: &Value) {
-    if url.query_pairs().count() >= 2 {
-        panic!("This method does not work with multiple parameters");
-    }
-
-    if let Some(parameters) = operation_object["parameters"].as_array() {
-        let len = parameters
-            .iter()
-            .filter(|p| p["in"].eq("query"))
-            .collect::<Vec<_>>()
-            .len();
-        if len >= 2 {
-            panic!("This method does not work with multiple parameters");
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -728,6 +712,27 @@ components:
         );
     }
 
+    #[test]
+    fn test_validate_query_parameters_with_multiple_parameters() {
+        let api_spec = APISpec::from_file(DEFAULT_SPEC_FILE);
+        api_spec
+            .validate_query_parameters(
+                "/certificates?offset=20&limit=10",
+                &api_spec.openapi["paths"]["/certificates"]["get"],
+            )
+            .map(|_apispec| ())
+            .unwrap();
+
+        let result = api_spec.validate_query_parameters(
+            "/certificates?offset=20&whatever=123",
+            &api_spec.openapi["paths"]["/certificates"]["get"],
+        );
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Unexpected query parameter 'whatever'",
+        );
+    }
+
     #[test]
     fn test_verify_conformity_with_expected_status() {
         APISpec::verify_conformity(
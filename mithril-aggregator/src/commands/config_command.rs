@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, anyhow};
+use clap::{Parser, Subcommand};
+use config::{ConfigBuilder, builder::DefaultState};
+use serde::Serialize;
+use slog::{Logger, debug};
+
+use mithril_cardano_node_chain::chain_observer::ChainObserverType;
+use mithril_common::StdResult;
+use mithril_common::crypto_helper::ProtocolGenesisVerificationKey;
+use mithril_doc::{Documenter, StructDoc};
+
+use crate::commands::serve_command::ServeCommand;
+use crate::http_server::cors::CorsPolicies;
+use crate::tools::url_sanitizer::SanitizedUrlWithTrailingSlash;
+use crate::{
+    ConfigurationSource, ExecutionEnvironment, ServeCommandConfiguration, SnapshotUploaderType,
+    extract_all,
+};
+
+/// Configuration tools
+#[derive(Parser, Debug, Clone)]
+pub struct ConfigCommand {
+    /// commands
+    #[clap(subcommand)]
+    pub config_subcommand: ConfigSubCommand,
+}
+
+impl ConfigCommand {
+    pub async fn execute(
+        &self,
+        root_logger: Logger,
+        config_builder: ConfigBuilder<DefaultState>,
+    ) -> StdResult<()> {
+        self.config_subcommand.execute(root_logger, config_builder).await
+    }
+
+    pub fn extract_config(command_path: String) -> HashMap<String, StructDoc> {
+        extract_all!(
+            command_path,
+            ConfigSubCommand,
+            Validate = { ValidateConfigSubCommand },
+        )
+    }
+}
+
+/// Configuration tools commands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum ConfigSubCommand {
+    /// Validate the configuration of the serve command without starting the aggregator.
+    Validate(ValidateConfigSubCommand),
+}
+
+impl ConfigSubCommand {
+    pub async fn execute(
+        &self,
+        root_logger: Logger,
+        config_builder: ConfigBuilder<DefaultState>,
+    ) -> StdResult<()> {
+        match self {
+            Self::Validate(cmd) => cmd.execute(root_logger, config_builder).await,
+        }
+    }
+}
+
+/// Configuration validate command
+#[derive(Parser, Debug, Clone)]
+pub struct ValidateConfigSubCommand {
+    /// Enable JSON output.
+    #[clap(long)]
+    json: bool,
+
+    // Arguments of the serve command, so the validated configuration is the one it would load
+    #[clap(flatten)]
+    serve_command: ServeCommand,
+}
+
+impl ValidateConfigSubCommand {
+    pub async fn execute(
+        &self,
+        root_logger: Logger,
+        config_builder: ConfigBuilder<DefaultState>,
+    ) -> StdResult<()> {
+        let config = self.load_configuration(config_builder)?;
+        debug!(root_logger, "VALIDATE CONFIG command"; "config" => format!("{config:?}"));
+
+        let report = ConfigurationReport::check(&config);
+        if self.json {
+            println!("{}", serde_json::to_string(&report)?);
+        } else {
+            println!("{report}");
+        }
+
+        if report.is_valid() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Invalid configuration: {} check(s) failed",
+                report.failed_checks().count()
+            ))
+        }
+    }
+
+    fn load_configuration(
+        &self,
+        config_builder: ConfigBuilder<DefaultState>,
+    ) -> StdResult<ServeCommandConfiguration> {
+        config_builder
+            .add_source(self.serve_command.clone())
+            .build()
+            .with_context(|| "configuration build error")?
+            .try_deserialize()
+            .with_context(|| "configuration deserialize error")
+    }
+
+    pub fn extract_config(command_path: String) -> HashMap<String, StructDoc> {
+        HashMap::from([(command_path, ServeCommandConfiguration::extract())])
+    }
+}
+
+/// Result of a single configuration check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigurationCheck {
+    /// Name of the checked configuration
+    pub name: &'static str,
+
+    /// Reason of the failure, `None` if the check passed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ConfigurationCheck {
+    fn new(name: &'static str, result: StdResult<()>) -> Self {
+        Self {
+            name,
+            error: result.err().map(|error| format!("{error:#}")),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Report of all the checks run against a configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigurationReport {
+    /// Checks run against the configuration
+    pub checks: Vec<ConfigurationCheck>,
+}
+
+impl ConfigurationReport {
+    /// Run the checks that would otherwise only fail when the aggregator builds its dependencies
+    pub fn check(config: &dyn ConfigurationSource) -> Self {
+        let mut checks = vec![
+            ConfigurationCheck::new("network", config.get_network().map(|_| ())),
+            ConfigurationCheck::new(
+                "signed_entity_types",
+                config.compute_allowed_signed_entity_types_discriminants().map(|_| ()),
+            ),
+            ConfigurationCheck::new("server_url", config.get_server_url().map(|_| ())),
+            ConfigurationCheck::new("server_tls", Self::check_server_tls(config)),
+            ConfigurationCheck::new(
+                "signature_registration_rate_limit",
+                config.get_signature_registration_rate_limit().map(|_| ()),
+            ),
+            ConfigurationCheck::new(
+                "cors_policy",
+                CorsPolicies::from_config(&config.cors_policy().unwrap_or_default()).map(|_| ()),
+            ),
+            ConfigurationCheck::new("db_directory", Self::check_db_directory(config)),
+            ConfigurationCheck::new(
+                "cardano_node_socket_path",
+                Self::check_cardano_node_socket_path(config),
+            ),
+            ConfigurationCheck::new(
+                "snapshot_bucket_name",
+                Self::check_snapshot_bucket_name(config),
+            ),
+        ];
+
+        if config.environment() == ExecutionEnvironment::Production {
+            checks.push(ConfigurationCheck::new(
+                "genesis_verification_key",
+                ProtocolGenesisVerificationKey::from_json_hex(&config.genesis_verification_key())
+                    .map(|_| ()),
+            ));
+        }
+
+        match config.leader_aggregator_endpoint() {
+            Some(endpoint) => checks.push(ConfigurationCheck::new(
+                "leader_aggregator_endpoint",
+                SanitizedUrlWithTrailingSlash::parse(&endpoint).map(|_| ()),
+            )),
+            None => checks.push(ConfigurationCheck::new(
+                "epoch_settings",
+                config
+                    .get_leader_aggregator_epoch_settings_configuration()
+                    .map(|_| ()),
+            )),
+        }
+
+        Self { checks }
+    }
+
+    fn check_server_tls(config: &dyn ConfigurationSource) -> StdResult<()> {
+        if let Some(tls_configuration) = config.get_server_tls_configuration()? {
+            tls_configuration.build_rustls_config()?;
+        }
+
+        Ok(())
+    }
+
+    fn check_db_directory(config: &dyn ConfigurationSource) -> StdResult<()> {
+        let db_directory = config.db_directory();
+        if !db_directory.is_dir() {
+            return Err(anyhow!(
+                "Cardano node database directory '{}' does not exist",
+                db_directory.display()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn check_cardano_node_socket_path(config: &dyn ConfigurationSource) -> StdResult<()> {
+        let socket_path = config.cardano_node_socket_path();
+        if !matches!(config.chain_observer_type(), ChainObserverType::Fake) && !socket_path.exists()
+        {
+            return Err(anyhow!(
+                "Cardano node socket '{}' does not exist",
+                socket_path.display()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn check_snapshot_bucket_name(config: &dyn ConfigurationSource) -> StdResult<()> {
+        if config.snapshot_uploader_type() == SnapshotUploaderType::Gcp
+            && config.snapshot_bucket_name().is_none()
+        {
+            return Err(anyhow!(
+                "Configuration `snapshot_bucket_name` is mandatory when `snapshot_uploader_type` is `gcp`"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check if all the checks passed
+    pub fn is_valid(&self) -> bool {
+        self.checks.iter().all(ConfigurationCheck::is_valid)
+    }
+
+    /// Iterate over the checks that failed
+    pub fn failed_checks(&self) -> impl Iterator<Item = &ConfigurationCheck> {
+        self.checks.iter().filter(|check| !check.is_valid())
+    }
+}
+
+impl std::fmt::Display for ConfigurationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for check in &self.checks {
+            match &check.error {
+                None => writeln!(f, "[OK]     {}", check.name)?,
+                Some(error) => writeln!(f, "[FAILED] {}: {error}", check.name)?,
+            }
+        }
+
+        if self.is_valid() {
+            write!(f, "Configuration is valid")
+        } else {
+            write!(f, "Configuration is invalid")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use mithril_common::temp_dir_create;
+
+    use crate::DefaultConfiguration;
+    use crate::configuration::{CorsPolicyConfig, SignatureRegistrationRateLimitConfig};
+
+    use super::*;
+
+    fn valid_configuration(tmp_path: PathBuf) -> ServeCommandConfiguration {
+        ServeCommandConfiguration {
+            db_directory: tmp_path.clone(),
+            ..ServeCommandConfiguration::new_sample(tmp_path)
+        }
+    }
+
+    fn failed_checks_names(report: &ConfigurationReport) -> Vec<&'static str> {
+        report.failed_checks().map(|check| check.name).collect()
+    }
+
+    #[test]
+    fn sample_configuration_is_valid() {
+        let config = valid_configuration(temp_dir_create!());
+
+        let report = ConfigurationReport::check(&config);
+
+        assert!(report.is_valid(), "{report}");
+    }
+
+    #[test]
+    fn report_each_invalid_configuration_value() {
+        let config = ServeCommandConfiguration {
+            network: "unknown".to_string(),
+            signed_entity_types: Some("NotASignedEntityType".to_string()),
+            db_directory: PathBuf::from("/non/existing/directory"),
+            snapshot_uploader_type: SnapshotUploaderType::Gcp,
+            snapshot_bucket_name: None,
+            signature_registration_rate_limit: Some(SignatureRegistrationRateLimitConfig {
+                max_per_party: 0,
+                window_in_seconds: 60,
+            }),
+            cors_policy: Some(CorsPolicyConfig {
+                allowed_origins: Some(vec!["not-an-origin".to_string()]),
+                ..CorsPolicyConfig::default()
+            }),
+            ..valid_configuration(temp_dir_create!())
+        };
+
+        let report = ConfigurationReport::check(&config);
+
+        assert_eq!(
+            vec![
+                "network",
+                "signed_entity_types",
+                "signature_registration_rate_limit",
+                "cors_policy",
+                "db_directory",
+                "snapshot_bucket_name",
+                "epoch_settings",
+            ],
+            failed_checks_names(&report)
+        );
+    }
+
+    #[test]
+    fn check_cardano_node_socket_only_when_not_using_fake_chain_observer() {
+        let tmp_path = temp_dir_create!();
+        let config = ServeCommandConfiguration {
+            chain_observer_type: ChainObserverType::Pallas,
+            cardano_node_socket_path: tmp_path.join("node.socket"),
+            ..valid_configuration(tmp_path)
+        };
+
+        let report = ConfigurationReport::check(&config);
+
+        assert_eq!(
+            vec!["cardano_node_socket_path"],
+            failed_checks_names(&report)
+        );
+    }
+
+    #[test]
+    fn check_genesis_verification_key_only_in_production() {
+        let tmp_path = temp_dir_create!();
+        let config = ServeCommandConfiguration {
+            genesis_verification_key: "invalid".to_string(),
+            ..valid_configuration(tmp_path)
+        };
+
+        let report = ConfigurationReport::check(&config);
+        assert!(report.is_valid(), "{report}");
+
+        let report = ConfigurationReport::check(&ServeCommandConfiguration {
+            environment: ExecutionEnvironment::Production,
+            ..config
+        });
+        assert_eq!(
+            vec!["genesis_verification_key"],
+            failed_checks_names(&report)
+        );
+    }
+
+    #[test]
+    fn check_leader_aggregator_endpoint_instead_of_epoch_settings_for_follower() {
+        let config = ServeCommandConfiguration {
+            leader_aggregator_endpoint: Some("not an url".to_string()),
+            protocol_parameters: None,
+            ..valid_configuration(temp_dir_create!())
+        };
+
+        let report = ConfigurationReport::check(&config);
+
+        assert_eq!(
+            vec!["leader_aggregator_endpoint"],
+            failed_checks_names(&report)
+        );
+    }
+
+    #[test]
+    fn validate_configuration_with_the_serve_command_arguments() {
+        let tmp_path = temp_dir_create!();
+        let config_file = serde_json::json!({
+            "environment": "Test",
+            "network": "devnet",
+            "cardano_cli_path": "cardano-cli",
+            "cardano_node_socket_path": tmp_path.join("node.socket"),
+            "cardano_node_version": "10.5.1",
+            "run_interval": 1000,
+            "db_directory": tmp_path,
+            "data_stores_directory": tmp_path.join("stores"),
+            "genesis_verification_key": "",
+            "chain_observer_type": "fake",
+            "snapshot_uploader_type": "local",
+            "ancillary_files_signer_config": { "type": "secret-key", "secret_key": "" },
+        });
+        let failed_checks_with_args = |args: &[&str]| {
+            let command =
+                ValidateConfigSubCommand::try_parse_from(["validate"].iter().chain(args)).unwrap();
+            let config_builder = config::Config::builder()
+                .add_source(DefaultConfiguration::default())
+                .add_source(config::File::from_str(
+                    &config_file.to_string(),
+                    config::FileFormat::Json,
+                ));
+            let config = command.load_configuration(config_builder).unwrap();
+
+            failed_checks_names(&ConfigurationReport::check(&config))
+        };
+
+        assert_eq!(vec!["epoch_settings"], failed_checks_with_args(&[]));
+        assert_eq!(
+            Vec::<&str>::new(),
+            failed_checks_with_args(&[
+                "--leader-aggregator-endpoint",
+                "http://leader.aggregator:8080"
+            ])
+        );
+        assert_eq!(
+            vec!["server_tls", "epoch_settings"],
+            failed_checks_with_args(&[
+                "--server-tls-certificate-path",
+                "/non/existing/server.crt",
+                "--server-tls-private-key-path",
+                "/non/existing/server.key"
+            ])
+        );
+    }
+
+    #[test]
+    fn serialize_report_without_error_for_passed_checks() {
+        let report = ConfigurationReport {
+            checks: vec![
+                ConfigurationCheck::new("passed", Ok(())),
+                ConfigurationCheck::new("failed", Err(anyhow!("error"))),
+            ],
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(
+            serde_json::json!({
+                "checks": [
+                    { "name": "passed" },
+                    { "name": "failed", "error": "error" },
+                ]
+            }),
+            json
+        );
+    }
+}
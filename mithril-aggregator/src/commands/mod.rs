@@ -1,4 +1,5 @@
 mod config_association;
+mod config_command;
 mod database_command;
 mod era_command;
 mod genesis_command;
@@ -24,6 +25,7 @@ pub enum MainCommand {
     Serve(serve_command::ServeCommand),
     Tools(tools_command::ToolsCommand),
     Database(database_command::DatabaseCommand),
+    Config(config_command::ConfigCommand),
     #[clap(alias("doc"), hide(true))]
     GenerateDoc(GenerateDocCommands),
 }
@@ -48,6 +50,7 @@ impl MainCommand {
             Self::Serve(cmd) => cmd.execute(root_logger, config_builder).await,
             Self::Tools(cmd) => cmd.execute(root_logger, config_builder).await,
             Self::Database(cmd) => cmd.execute(root_logger, config_builder).await,
+            Self::Config(cmd) => cmd.execute(root_logger, config_builder).await,
             Self::GenerateDoc(cmd) => {
                 let commands_configs =
                     Self::extract_config(Self::format_crate_name_to_config_key());
@@ -62,6 +65,7 @@ impl MainCommand {
         extract_all!(
             command_path,
             MainCommand,
+            Config = { config_command::ConfigCommand },
             Database = { database_command::DatabaseCommand },
             Era = { era_command::EraCommand },
             Genesis = { genesis_command::GenesisCommand },
@@ -82,6 +86,7 @@ impl MainCommand {
             MainCommand::Era(_) => CommandType::CommandLine,
             MainCommand::Tools(_) => CommandType::CommandLine,
             MainCommand::Database(_) => CommandType::CommandLine,
+            MainCommand::Config(_) => CommandType::CommandLine,
             MainCommand::GenerateDoc(_) => CommandType::CommandLine,
         }
     }
@@ -1,6 +1,6 @@
 use std::{
     collections::HashMap,
-    net::IpAddr,
+    net::{IpAddr, SocketAddr},
     path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
@@ -13,7 +13,7 @@ use clap::Parser;
 use config::{ConfigBuilder, Map, Source, Value, builder::DefaultState};
 
 use slog::{Logger, crit, debug, info, warn};
-use tokio::task::JoinSet;
+use tokio::{net::TcpListener, task::JoinSet};
 
 use mithril_cli_helper::{
     register_config_value, register_config_value_bool, register_config_value_option,
@@ -23,7 +23,8 @@ use mithril_doc::{Documenter, DocumenterDefault, StructDoc};
 use mithril_metric::MetricsServer;
 
 use crate::{
-    DefaultConfiguration, ServeCommandConfiguration, dependency_injection::DependenciesBuilder,
+    ConfigurationSource, DefaultConfiguration, ServeCommandConfiguration,
+    dependency_injection::DependenciesBuilder, http_server::tls::serve_with_tls,
     tools::VacuumTracker,
 };
 
@@ -80,6 +81,22 @@ pub struct ServeCommand {
     /// If this is not set, the aggregator will run in a leader mode.
     #[clap(long)]
     leader_aggregator_endpoint: Option<String>,
+
+    /// Path of the PEM encoded certificate chain used to serve HTTPS.
+    ///
+    /// Requires `--server-tls-private-key-path` to be set.
+    #[clap(long)]
+    server_tls_certificate_path: Option<PathBuf>,
+
+    /// Path of the PEM encoded private key used to serve HTTPS.
+    #[clap(long)]
+    server_tls_private_key_path: Option<PathBuf>,
+
+    /// Path of the PEM encoded certificates of the authorities trusted to issue client certificates.
+    ///
+    /// If set, clients must authenticate with a certificate (mutual TLS).
+    #[clap(long)]
+    server_tls_client_ca_certificate_path: Option<PathBuf>,
 }
 
 impl Source for ServeCommand {
@@ -111,6 +128,24 @@ impl Source for ServeCommand {
             self.leader_aggregator_endpoint,
             |v: String| { Some(v) }
         );
+        register_config_value_option!(
+            result,
+            &namespace,
+            self.server_tls_certificate_path,
+            |v: PathBuf| format!("{}", v.to_string_lossy())
+        );
+        register_config_value_option!(
+            result,
+            &namespace,
+            self.server_tls_private_key_path,
+            |v: PathBuf| format!("{}", v.to_string_lossy())
+        );
+        register_config_value_option!(
+            result,
+            &namespace,
+            self.server_tls_client_ca_certificate_path,
+            |v: PathBuf| format!("{}", v.to_string_lossy())
+        );
 
         Ok(result)
     }
@@ -180,19 +215,48 @@ impl ServeCommand {
             .create_http_routes()
             .await
             .with_context(|| "Dependencies Builder can not create http routes")?;
+        let server_tls_config = config
+            .get_server_tls_configuration()
+            .and_then(|tls_configuration| {
+                tls_configuration
+                    .map(|tls_configuration| tls_configuration.build_rustls_config())
+                    .transpose()
+            })
+            .with_context(|| "Invalid HTTP server TLS configuration")?;
+        let server_address = SocketAddr::new(
+            config.server_ip.clone().parse::<IpAddr>().unwrap(),
+            config.server_port,
+        );
+        let http_server_logger = root_logger.clone();
         let mut stop_rx_clone = stop_rx.clone();
         join_set.spawn(async move {
-            warp::serve(routes)
-                .bind((
-                    config.server_ip.clone().parse::<IpAddr>().unwrap(),
-                    config.server_port,
-                ))
-                .await
-                .graceful(async move {
-                    stop_rx_clone.changed().await.ok();
-                })
-                .run()
-                .await;
+            let shutdown_signal = async move {
+                stop_rx_clone.changed().await.ok();
+            };
+
+            match server_tls_config {
+                Some(tls_config) => {
+                    let listener = TcpListener::bind(server_address)
+                        .await
+                        .map_err(|e| format!("HTTP server can not bind {server_address}: {e}"))?;
+                    serve_with_tls(
+                        routes,
+                        listener,
+                        tls_config,
+                        shutdown_signal,
+                        http_server_logger,
+                    )
+                    .await;
+                }
+                None => {
+                    warp::serve(routes)
+                        .bind(server_address)
+                        .await
+                        .graceful(shutdown_signal)
+                        .run()
+                        .await;
+                }
+            }
 
             Ok(())
         });
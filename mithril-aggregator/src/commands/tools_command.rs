@@ -5,7 +5,10 @@ use serde::Deserialize;
 use slog::{Logger, debug};
 use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
-use mithril_common::StdResult;
+use mithril_common::{
+    StdResult, crypto_helper::ProtocolGenesisVerificationKey,
+    entities::HexEncodedGenesisVerificationKey,
+};
 use mithril_doc::{Documenter, StructDoc};
 use mithril_persistence::sqlite::{SqliteCleaner, SqliteCleaningTask};
 
@@ -14,7 +17,7 @@ use crate::{
     database::repository::{CertificateRepository, SignedEntityStore},
     dependency_injection::DependenciesBuilder,
     extract_all,
-    tools::CertificatesHashMigrator,
+    tools::{CertificatesBundleTools, CertificatesHashMigrator},
 };
 
 #[derive(Debug, Clone, Deserialize, Documenter)]
@@ -56,6 +59,8 @@ impl ToolsCommand {
             command_path,
             ToolsSubCommand,
             RecomputeCertificatesHash = { RecomputeCertificatesHashCommand },
+            ExportCertificates = { ExportCertificatesCommand },
+            ImportCertificates = { ImportCertificatesCommand },
         )
     }
 }
@@ -69,6 +74,14 @@ pub enum ToolsSubCommand {
     /// Since it will modify the aggregator sqlite database it's strongly recommended to backup it
     /// before running this command.
     RecomputeCertificatesHash(RecomputeCertificatesHashCommand),
+
+    /// Export all certificates of the database to a bundle file.
+    ExportCertificates(ExportCertificatesCommand),
+
+    /// Import the certificates of a bundle file in a database without certificates.
+    ///
+    /// The certificate chains of the bundle are verified before any certificate is stored.
+    ImportCertificates(ImportCertificatesCommand),
 }
 
 impl ToolsSubCommand {
@@ -79,6 +92,8 @@ impl ToolsSubCommand {
     ) -> StdResult<()> {
         match self {
             Self::RecomputeCertificatesHash(cmd) => cmd.execute(root_logger, config_builder).await,
+            Self::ExportCertificates(cmd) => cmd.execute(root_logger, config_builder).await,
+            Self::ImportCertificates(cmd) => cmd.execute(root_logger, config_builder).await,
         }
     }
 }
@@ -134,6 +149,111 @@ impl RecomputeCertificatesHashCommand {
     }
 }
 
+/// Export certificates command.
+#[derive(Parser, Debug, Clone)]
+pub struct ExportCertificatesCommand {
+    /// Target path of the certificates bundle file
+    #[clap(long)]
+    target_path: PathBuf,
+}
+
+impl ExportCertificatesCommand {
+    pub async fn execute(
+        &self,
+        root_logger: Logger,
+        config_builder: ConfigBuilder<DefaultState>,
+    ) -> StdResult<()> {
+        let config: ToolsCommandConfiguration = config_builder
+            .build()
+            .with_context(|| "configuration build error")?
+            .try_deserialize()
+            .with_context(|| "configuration deserialize error")?;
+        debug!(root_logger, "EXPORT CERTIFICATES command"; "config" => format!("{config:?}"));
+        println!("Exporting certificates to {}", self.target_path.display());
+        let mut dependencies_builder =
+            DependenciesBuilder::new(root_logger.clone(), Arc::new(config.clone()));
+
+        let dependencies_container = dependencies_builder
+            .create_tools_command_container()
+            .await
+            .with_context(|| "Failed to create the tools command dependencies container")?;
+
+        let certificates_bundle_tools = CertificatesBundleTools::new(
+            Arc::new(CertificateRepository::new(
+                dependencies_container.db_connection.clone(),
+            )),
+            root_logger,
+        );
+        let total_certificates = certificates_bundle_tools
+            .export_certificates(&self.target_path)
+            .await
+            .with_context(|| "export-certificates: export error")?;
+        println!("{total_certificates} certificate(s) exported");
+
+        Ok(())
+    }
+
+    pub fn extract_config(command_path: String) -> HashMap<String, StructDoc> {
+        HashMap::from([(command_path, ToolsCommandConfiguration::extract())])
+    }
+}
+
+/// Import certificates command.
+#[derive(Parser, Debug, Clone)]
+pub struct ImportCertificatesCommand {
+    /// Path of the certificates bundle file
+    #[clap(long)]
+    source_path: PathBuf,
+
+    /// Genesis Verification Key
+    #[clap(long)]
+    genesis_verification_key: HexEncodedGenesisVerificationKey,
+}
+
+impl ImportCertificatesCommand {
+    pub async fn execute(
+        &self,
+        root_logger: Logger,
+        config_builder: ConfigBuilder<DefaultState>,
+    ) -> StdResult<()> {
+        let config: ToolsCommandConfiguration = config_builder
+            .build()
+            .with_context(|| "configuration build error")?
+            .try_deserialize()
+            .with_context(|| "configuration deserialize error")?;
+        debug!(root_logger, "IMPORT CERTIFICATES command"; "config" => format!("{config:?}"));
+        println!("Importing certificates from {}", self.source_path.display());
+        let mut dependencies_builder =
+            DependenciesBuilder::new(root_logger.clone(), Arc::new(config.clone()));
+
+        let dependencies_container = dependencies_builder
+            .create_tools_command_container()
+            .await
+            .with_context(|| "Failed to create the tools command dependencies container")?;
+
+        let certificates_bundle_tools = CertificatesBundleTools::new(
+            Arc::new(CertificateRepository::new(
+                dependencies_container.db_connection.clone(),
+            )),
+            root_logger,
+        );
+        let total_certificates = certificates_bundle_tools
+            .import_certificates(
+                &self.source_path,
+                &ProtocolGenesisVerificationKey::from_json_hex(&self.genesis_verification_key)?,
+            )
+            .await
+            .with_context(|| "import-certificates: import error")?;
+        println!("{total_certificates} certificate(s) imported");
+
+        Ok(())
+    }
+
+    pub fn extract_config(command_path: String) -> HashMap<String, StructDoc> {
+        HashMap::from([(command_path, ToolsCommandConfiguration::extract())])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -2,7 +2,7 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use anyhow::Context;
+use anyhow::{Context, anyhow};
 use config::{ConfigError, Map, Source, Value, ValueKind};
 use serde::Deserialize;
 
@@ -21,6 +21,7 @@ use mithril_era::adapters::EraReaderAdapterType;
 
 use crate::entities::AggregatorEpochSettings;
 use crate::http_server::SERVER_BASE_PATH;
+use crate::http_server::tls::ServerTlsConfiguration;
 use crate::services::ancillary_signer::GcpCryptoKeyVersionResourceName;
 use crate::tools::DEFAULT_GCP_CREDENTIALS_JSON_ENV_VAR;
 use crate::tools::url_sanitizer::SanitizedUrlWithTrailingSlash;
@@ -141,6 +142,21 @@ pub trait ConfigurationSource {
         panic!("public_server_url is not implemented.");
     }
 
+    /// Path of the PEM encoded certificate chain used by the server to serve HTTPS
+    fn server_tls_certificate_path(&self) -> Option<PathBuf> {
+        panic!("server_tls_certificate_path is not implemented.");
+    }
+
+    /// Path of the PEM encoded private key used by the server to serve HTTPS
+    fn server_tls_private_key_path(&self) -> Option<PathBuf> {
+        panic!("server_tls_private_key_path is not implemented.");
+    }
+
+    /// Path of the PEM encoded certificates of the authorities trusted to issue client certificates
+    fn server_tls_client_ca_certificate_path(&self) -> Option<PathBuf> {
+        panic!("server_tls_client_ca_certificate_path is not implemented.");
+    }
+
     /// Run Interval is the interval between two runtime cycles in ms
     fn run_interval(&self) -> u64 {
         panic!("run_interval is not implemented.");
@@ -310,6 +326,48 @@ pub trait ConfigurationSource {
         panic!("get_server_url is not implemented.");
     }
 
+    /// Get the TLS configuration of the server, `None` if it serves plain HTTP.
+    fn get_server_tls_configuration(&self) -> StdResult<Option<ServerTlsConfiguration>> {
+        match (
+            self.server_tls_certificate_path(),
+            self.server_tls_private_key_path(),
+            self.server_tls_client_ca_certificate_path(),
+        ) {
+            (None, None, None) => Ok(None),
+            (Some(certificate_path), Some(private_key_path), client_ca_certificate_path) => {
+                Ok(Some(ServerTlsConfiguration {
+                    certificate_path,
+                    private_key_path,
+                    client_ca_certificate_path,
+                }))
+            }
+            _ => Err(anyhow!(
+                "Configurations `server_tls_certificate_path` and `server_tls_private_key_path` are mandatory to serve HTTPS"
+            )),
+        }
+    }
+
+    /// Get the rate limit applied to the signature registrations, `None` if they are not rate limited.
+    fn get_signature_registration_rate_limit(
+        &self,
+    ) -> StdResult<Option<SignatureRegistrationRateLimitConfig>> {
+        let Some(rate_limit) = self.signature_registration_rate_limit() else {
+            return Ok(None);
+        };
+        if rate_limit.max_per_party == 0 {
+            return Err(anyhow!(
+                "Configuration `signature_registration_rate_limit.max_per_party` must be greater than 0"
+            ));
+        }
+        if rate_limit.window_in_seconds == 0 {
+            return Err(anyhow!(
+                "Configuration `signature_registration_rate_limit.window_in_seconds` must be greater than 0"
+            ));
+        }
+
+        Ok(Some(rate_limit))
+    }
+
     /// Get a representation of the Cardano network.
     fn get_network(&self) -> StdResult<CardanoNetwork> {
         CardanoNetwork::from_code(self.network(), self.network_magic())
@@ -430,6 +488,21 @@ pub trait ConfigurationSource {
     fn signature_processor_wait_delay_on_error_ms(&self) -> u64 {
         panic!("signature_processor_wait_delay_on_error_ms is not implemented.");
     }
+
+    /// Rate limit applied to the signature registrations received by the HTTP server
+    fn signature_registration_rate_limit(&self) -> Option<SignatureRegistrationRateLimitConfig> {
+        panic!("signature_registration_rate_limit is not implemented.");
+    }
+
+    /// CORS policy of the HTTP server
+    fn cors_policy(&self) -> Option<CorsPolicyConfig> {
+        panic!("cors_policy is not implemented.");
+    }
+
+    /// Request and response payload policy of the HTTP server
+    fn payload_policy(&self) -> Option<PayloadPolicyConfig> {
+        panic!("payload_policy is not implemented.");
+    }
 }
 
 /// Serve command configuration
@@ -499,6 +572,20 @@ pub struct ServeCommandConfiguration {
     /// Server URL that can be accessed from the outside
     pub public_server_url: Option<String>,
 
+    /// Path of the PEM encoded certificate chain used by the server to serve HTTPS, requires
+    /// `server_tls_private_key_path` to be set
+    #[example = "`/etc/mithril/tls/server.crt`"]
+    pub server_tls_certificate_path: Option<PathBuf>,
+
+    /// Path of the PEM encoded private key used by the server to serve HTTPS
+    #[example = "`/etc/mithril/tls/server.key`"]
+    pub server_tls_private_key_path: Option<PathBuf>,
+
+    /// Path of the PEM encoded certificates of the authorities trusted to issue client
+    /// certificates, if set clients must authenticate with a certificate (mutual TLS)
+    #[example = "`/etc/mithril/tls/client-ca.crt`"]
+    pub server_tls_client_ca_certificate_path: Option<PathBuf>,
+
     /// Run Interval is the interval between two runtime cycles in ms
     #[example = "`60000`"]
     pub run_interval: u64,
@@ -633,6 +720,35 @@ pub struct ServeCommandConfiguration {
 
     /// Delay to wait between two signature processing attempts after an error
     pub signature_processor_wait_delay_on_error_ms: u64,
+
+    /// Rate limit applied to the signature registrations received by the HTTP server, if not set
+    /// the registrations are not rate limited.
+    ///
+    /// Only the authenticated registrations are limited, the ones failing authentication must be
+    /// limited upstream, for example by a reverse proxy.
+    #[example = "`{ \"max_per_party\": 20, \"window_in_seconds\": 60 }`"]
+    #[serde(
+        default,
+        deserialize_with = "serde_deserialization::string_or_struct_optional"
+    )]
+    pub signature_registration_rate_limit: Option<SignatureRegistrationRateLimitConfig>,
+
+    /// CORS policy of the HTTP server, if not set any origin is allowed on every route.
+    #[example = "`{ \"allowed_origins\": [\"https://mithril.network\"], \"registration_allowed_origins\": [] }`"]
+    #[serde(
+        default,
+        deserialize_with = "serde_deserialization::string_or_struct_optional"
+    )]
+    pub cors_policy: Option<CorsPolicyConfig>,
+
+    /// Request and response payload policy of the HTTP server, if not set request bodies are
+    /// limited to 256 KiB and are neither decompressed nor compressed.
+    #[example = "`{ \"registration_max_body_size_in_bytes\": 1048576, \"request_decompression\": true, \"response_compression\": true }`"]
+    #[serde(
+        default,
+        deserialize_with = "serde_deserialization::string_or_struct_optional"
+    )]
+    pub payload_policy: Option<PayloadPolicyConfig>,
 }
 
 /// Uploader needed to copy the snapshot once computed.
@@ -706,6 +822,86 @@ pub enum AncillaryFilesSignerConfig {
     },
 }
 
+/// Rate limit applied to the signature registrations received by the HTTP server.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub struct SignatureRegistrationRateLimitConfig {
+    /// Maximum number of signature registrations accepted for a single party during a window.
+    ///
+    /// Signers send one signature per signed entity type and per round, this limit should
+    /// leave room for those and for their retries. Must be greater than 0.
+    pub max_per_party: u32,
+
+    /// Duration of the rate limiting window, in seconds. Must be greater than 0.
+    pub window_in_seconds: u64,
+}
+
+impl FromStr for SignatureRegistrationRateLimitConfig {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// CORS policy of the HTTP server.
+///
+/// The policy only restricts browsers: requests without an `Origin` header, like the ones sent
+/// by the signers, are not subject to it.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct CorsPolicyConfig {
+    /// Origins allowed to call the routes (e.g. `https://mithril.network`), if not set any
+    /// origin is allowed.
+    pub allowed_origins: Option<Vec<String>>,
+
+    /// Origins allowed to call the signer and signature registration routes, if not set
+    /// `allowed_origins` applies. An empty list forbids every origin.
+    pub registration_allowed_origins: Option<Vec<String>>,
+
+    /// Request headers allowed in addition to `content-type` and the Mithril headers.
+    #[serde(default)]
+    pub additional_allowed_headers: Vec<String>,
+
+    /// Duration, in seconds, during which browsers can cache the response of a preflight request.
+    pub max_age_in_seconds: Option<u64>,
+}
+
+impl FromStr for CorsPolicyConfig {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Request and response payload policy of the HTTP server.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct PayloadPolicyConfig {
+    /// Maximum size, in bytes, of the request bodies, default to 262144 (256 KiB).
+    pub max_body_size_in_bytes: Option<u64>,
+
+    /// Maximum size, in bytes, of the signer and signature registration request bodies, if not
+    /// set `max_body_size_in_bytes` applies.
+    pub registration_max_body_size_in_bytes: Option<u64>,
+
+    /// Accept request bodies compressed with gzip or deflate, the size limits apply both to the
+    /// compressed and to the decompressed bodies.
+    #[serde(default)]
+    pub request_decompression: bool,
+
+    /// Compress the responses of the routes used by the clients with gzip or deflate when they
+    /// accept it, except the archives downloads which are already compressed.
+    #[serde(default)]
+    pub response_compression: bool,
+}
+
+impl FromStr for PayloadPolicyConfig {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
 fn default_gcp_kms_credentials_json_env_var() -> String {
     DEFAULT_GCP_CREDENTIALS_JSON_ENV_VAR.to_string()
 }
@@ -748,6 +944,9 @@ impl ServeCommandConfiguration {
             server_ip: "0.0.0.0".to_string(),
             server_port: 8000,
             public_server_url: None,
+            server_tls_certificate_path: None,
+            server_tls_private_key_path: None,
+            server_tls_client_ca_certificate_path: None,
             run_interval: 5000,
             db_directory: PathBuf::new(),
             // Note: this is a band-aid solution to avoid IO operations in the `mithril-aggregator`
@@ -790,13 +989,21 @@ impl ServeCommandConfiguration {
             custom_origin_tag_white_list: None,
             aggregate_signature_type: AggregateSignatureType::Concatenation,
             signature_processor_wait_delay_on_error_ms: 5000,
+            signature_registration_rate_limit: None,
+            cors_policy: None,
+            payload_policy: None,
         }
     }
 
     /// Build the local server URL from configuration.
     pub fn get_local_server_url(&self) -> StdResult<SanitizedUrlWithTrailingSlash> {
+        let scheme = if self.server_tls_certificate_path.is_some() {
+            "https"
+        } else {
+            "http"
+        };
         SanitizedUrlWithTrailingSlash::parse(&format!(
-            "http://{}:{}/{SERVER_BASE_PATH}/",
+            "{scheme}://{}:{}/{SERVER_BASE_PATH}/",
             self.server_ip, self.server_port
         ))
     }
@@ -867,6 +1074,18 @@ impl ConfigurationSource for ServeCommandConfiguration {
         self.public_server_url.clone()
     }
 
+    fn server_tls_certificate_path(&self) -> Option<PathBuf> {
+        self.server_tls_certificate_path.clone()
+    }
+
+    fn server_tls_private_key_path(&self) -> Option<PathBuf> {
+        self.server_tls_private_key_path.clone()
+    }
+
+    fn server_tls_client_ca_certificate_path(&self) -> Option<PathBuf> {
+        self.server_tls_client_ca_certificate_path.clone()
+    }
+
     fn run_interval(&self) -> u64 {
         self.run_interval
     }
@@ -997,6 +1216,18 @@ impl ConfigurationSource for ServeCommandConfiguration {
     fn signature_processor_wait_delay_on_error_ms(&self) -> u64 {
         self.signature_processor_wait_delay_on_error_ms
     }
+
+    fn signature_registration_rate_limit(&self) -> Option<SignatureRegistrationRateLimitConfig> {
+        self.signature_registration_rate_limit
+    }
+
+    fn cors_policy(&self) -> Option<CorsPolicyConfig> {
+        self.cors_policy.clone()
+    }
+
+    fn payload_policy(&self) -> Option<PayloadPolicyConfig> {
+        self.payload_policy.clone()
+    }
 }
 
 /// Default configuration with all the default values for configurations.
@@ -1323,6 +1554,102 @@ mod test {
         );
     }
 
+    #[test]
+    fn get_server_url_return_local_https_url_if_tls_is_enabled() {
+        let config = ServeCommandConfiguration {
+            server_ip: "1.2.3.4".to_string(),
+            server_port: 5678,
+            public_server_url: None,
+            server_tls_certificate_path: Some(PathBuf::from("server.crt")),
+            server_tls_private_key_path: Some(PathBuf::from("server.key")),
+            ..ServeCommandConfiguration::new_sample(temp_dir!())
+        };
+
+        assert_eq!(
+            config.get_server_url().unwrap().as_str(),
+            &format!("https://1.2.3.4:5678/{SERVER_BASE_PATH}/")
+        );
+    }
+
+    #[test]
+    fn get_server_tls_configuration_requires_both_certificate_and_private_key() {
+        let config = ServeCommandConfiguration::new_sample(temp_dir!());
+        assert_eq!(None, config.get_server_tls_configuration().unwrap());
+
+        let config = ServeCommandConfiguration {
+            server_tls_certificate_path: Some(PathBuf::from("server.crt")),
+            server_tls_private_key_path: Some(PathBuf::from("server.key")),
+            server_tls_client_ca_certificate_path: Some(PathBuf::from("ca.crt")),
+            ..ServeCommandConfiguration::new_sample(temp_dir!())
+        };
+        assert_eq!(
+            Some(ServerTlsConfiguration {
+                certificate_path: PathBuf::from("server.crt"),
+                private_key_path: PathBuf::from("server.key"),
+                client_ca_certificate_path: Some(PathBuf::from("ca.crt")),
+            }),
+            config.get_server_tls_configuration().unwrap()
+        );
+
+        for (certificate_path, private_key_path, client_ca_certificate_path) in [
+            (Some("server.crt"), None, None),
+            (None, Some("server.key"), None),
+            (None, None, Some("ca.crt")),
+        ] {
+            let config = ServeCommandConfiguration {
+                server_tls_certificate_path: certificate_path.map(PathBuf::from),
+                server_tls_private_key_path: private_key_path.map(PathBuf::from),
+                server_tls_client_ca_certificate_path: client_ca_certificate_path
+                    .map(PathBuf::from),
+                ..ServeCommandConfiguration::new_sample(temp_dir!())
+            };
+            config
+                .get_server_tls_configuration()
+                .expect_err("Should fail when the certificate or the private key is missing");
+        }
+    }
+
+    #[test]
+    fn get_signature_registration_rate_limit_rejects_zero_values() {
+        let config = ServeCommandConfiguration::new_sample(temp_dir!());
+        assert_eq!(
+            None,
+            config.get_signature_registration_rate_limit().unwrap()
+        );
+
+        let rate_limit = SignatureRegistrationRateLimitConfig {
+            max_per_party: 20,
+            window_in_seconds: 60,
+        };
+        let config = ServeCommandConfiguration {
+            signature_registration_rate_limit: Some(rate_limit),
+            ..ServeCommandConfiguration::new_sample(temp_dir!())
+        };
+        assert_eq!(
+            Some(rate_limit),
+            config.get_signature_registration_rate_limit().unwrap()
+        );
+
+        for invalid_rate_limit in [
+            SignatureRegistrationRateLimitConfig {
+                max_per_party: 0,
+                ..rate_limit
+            },
+            SignatureRegistrationRateLimitConfig {
+                window_in_seconds: 0,
+                ..rate_limit
+            },
+        ] {
+            let config = ServeCommandConfiguration {
+                signature_registration_rate_limit: Some(invalid_rate_limit),
+                ..ServeCommandConfiguration::new_sample(temp_dir!())
+            };
+            config
+                .get_signature_registration_rate_limit()
+                .expect_err("Should fail when the rate limit has a zero value");
+        }
+    }
+
     #[test]
     fn joining_to_local_server_url_keep_base_path() {
         let config = ServeCommandConfiguration {
@@ -1542,6 +1869,53 @@ mod test {
         }
     }
 
+    #[test]
+    fn deserializing_signature_registration_rate_limit_config() {
+        let deserialized: SignatureRegistrationRateLimitConfig =
+            serde_json::from_str(r#"{ "max_per_party": 20, "window_in_seconds": 60 }"#).unwrap();
+        assert_eq!(
+            deserialized,
+            SignatureRegistrationRateLimitConfig {
+                max_per_party: 20,
+                window_in_seconds: 60,
+            }
+        );
+    }
+
+    #[test]
+    fn deserializing_cors_policy_config() {
+        let deserialized: CorsPolicyConfig = serde_json::from_str(
+            r#"{ "allowed_origins": ["https://mithril.network"], "registration_allowed_origins": [] }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            deserialized,
+            CorsPolicyConfig {
+                allowed_origins: Some(vec!["https://mithril.network".to_string()]),
+                registration_allowed_origins: Some(vec![]),
+                additional_allowed_headers: vec![],
+                max_age_in_seconds: None,
+            }
+        );
+    }
+
+    #[test]
+    fn deserializing_payload_policy_config() {
+        let deserialized: PayloadPolicyConfig = serde_json::from_str(
+            r#"{ "registration_max_body_size_in_bytes": 1048576, "request_decompression": true }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            deserialized,
+            PayloadPolicyConfig {
+                max_body_size_in_bytes: None,
+                registration_max_body_size_in_bytes: Some(1048576),
+                request_decompression: true,
+                response_compression: false,
+            }
+        );
+    }
+
     mod origin_tag {
         use super::*;
 
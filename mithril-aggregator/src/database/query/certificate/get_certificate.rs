@@ -11,18 +11,23 @@ use crate::database::record::CertificateRecord;
 /// Simple queries to retrieve [CertificateRecord] from the sqlite database.
 pub struct GetCertificateRecordQuery {
     condition: WhereCondition,
+    /// Maximum number of records to return and number of records to skip
+    limit_and_offset: Option<(i64, i64)>,
 }
 
 impl GetCertificateRecordQuery {
+    #[cfg(test)]
     pub fn all() -> Self {
         Self {
             condition: WhereCondition::default(),
+            limit_and_offset: None,
         }
     }
 
     pub fn all_genesis() -> Self {
         Self {
             condition: WhereCondition::new("parent_certificate_id is null", vec![]),
+            limit_and_offset: None,
         }
     }
 
@@ -32,6 +37,18 @@ impl GetCertificateRecordQuery {
                 "certificate_id = ?*",
                 vec![Value::String(certificate_id.to_owned())],
             ),
+            limit_and_offset: None,
+        }
+    }
+
+    /// At most `limit` records, from the latest to the oldest, skipping the `offset` latest ones
+    pub fn latest_page(offset: usize, limit: usize) -> Self {
+        Self {
+            condition: WhereCondition::default(),
+            limit_and_offset: Some((
+                i64::try_from(limit).unwrap_or(i64::MAX),
+                i64::try_from(offset).unwrap_or(i64::MAX),
+            )),
         }
     }
 
@@ -39,6 +56,7 @@ impl GetCertificateRecordQuery {
     pub fn by_epoch(epoch: Epoch) -> StdResult<Self> {
         Ok(Self {
             condition: WhereCondition::new("epoch = ?*", vec![Value::Integer(epoch.try_into()?)]),
+            limit_and_offset: None,
         })
     }
 }
@@ -53,7 +71,13 @@ impl Query for GetCertificateRecordQuery {
     fn get_definition(&self, condition: &str) -> String {
         let aliases = SourceAlias::new(&[("{:certificate:}", "c")]);
         let projection = Self::Entity::get_projection().expand(aliases);
-        format!("select {projection} from certificate as c where {condition} order by ROWID desc")
+        let pagination = self
+            .limit_and_offset
+            .map(|(limit, offset)| format!(" limit {limit} offset {offset}"))
+            .unwrap_or_default();
+        format!(
+            "select {projection} from certificate as c where {condition} order by ROWID desc{pagination}"
+        )
     }
 }
 
@@ -119,6 +143,37 @@ mod tests {
         assert_eq!(expected_certificate_records, certificate_records);
     }
 
+    #[test]
+    fn test_get_latest_page_of_certificate_records() {
+        let certificates = setup_certificate_chain(5, 2);
+        let expected_certificate_records: Vec<CertificateRecord> = certificates
+            .reversed_chain()
+            .into_iter()
+            .map(|c| c.try_into().unwrap())
+            .collect();
+
+        let connection = main_db_connection().unwrap();
+        insert_certificate_records(&connection, certificates.certificates_chained.clone());
+
+        let certificate_records: Vec<CertificateRecord> = connection
+            .fetch_collect(GetCertificateRecordQuery::latest_page(1, 2))
+            .unwrap();
+        assert_eq!(expected_certificate_records[1..3], certificate_records);
+
+        let certificate_records: Vec<CertificateRecord> = connection
+            .fetch_collect(GetCertificateRecordQuery::latest_page(4, 2))
+            .unwrap();
+        assert_eq!(expected_certificate_records[4..], certificate_records);
+
+        let certificate_records: Vec<CertificateRecord> = connection
+            .fetch_collect(GetCertificateRecordQuery::latest_page(
+                usize::MAX,
+                usize::MAX,
+            ))
+            .unwrap();
+        assert_eq!(Vec::<CertificateRecord>::new(), certificate_records);
+    }
+
     #[test]
     fn test_get_all_genesis_certificate_records() {
         // Two chains with different protocol parameters so generated certificates are different.
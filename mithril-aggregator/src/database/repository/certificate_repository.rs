@@ -46,12 +46,25 @@ impl CertificateRepository {
         T: TryFrom<CertificateRecord>,
         T::Error: Into<StdError>,
     {
-        let cursor = self.connection.fetch(GetCertificateRecordQuery::all())?;
+        self.get_latest_certificates_page(0, last_n).await
+    }
+
+    /// Return at most `limit` certificates, from the latest to the oldest, skipping the
+    /// `offset` latest ones.
+    pub async fn get_latest_certificates_page<T>(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> StdResult<Vec<T>>
+    where
+        T: TryFrom<CertificateRecord>,
+        T::Error: Into<StdError>,
+    {
+        let cursor = self
+            .connection
+            .fetch(GetCertificateRecordQuery::latest_page(offset, limit))?;
 
-        cursor
-            .take(last_n)
-            .map(|c| c.try_into().map_err(Into::into))
-            .collect()
+        cursor.map(|c| c.try_into().map_err(Into::into)).collect()
     }
 
     /// Return the latest genesis certificate.
@@ -312,6 +325,25 @@ mod tests {
         assert_eq!(certificates.reversed_chain(), latest_certificates);
     }
 
+    #[tokio::test]
+    async fn repository_get_latest_certificates_page() {
+        let certificates = setup_certificate_chain(5, 2);
+        let connection = Arc::new(main_db_connection().unwrap());
+        insert_certificate_records(&connection, certificates.certificates_chained.clone());
+
+        let repository = CertificateRepository::new(connection);
+        let expected_certificates = certificates.reversed_chain();
+
+        let page: Vec<Certificate> = repository.get_latest_certificates_page(1, 2).await.unwrap();
+        assert_eq!(expected_certificates[1..3].to_vec(), page);
+
+        let page: Vec<Certificate> = repository.get_latest_certificates_page(4, 2).await.unwrap();
+        assert_eq!(expected_certificates[4..].to_vec(), page);
+
+        let page: Vec<Certificate> = repository.get_latest_certificates_page(5, 2).await.unwrap();
+        assert_eq!(Vec::<Certificate>::new(), page);
+    }
+
     #[tokio::test]
     async fn repository_get_latest_genesis_certificate() {
         let certificates = setup_certificate_chain(5, 2);
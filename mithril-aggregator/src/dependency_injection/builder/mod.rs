@@ -50,7 +50,11 @@ use crate::{
     },
     event_store::{EventMessage, TransmitterService},
     file_uploaders::FileUploader,
-    http_server::routes::router::{self, RouterConfig, RouterState},
+    http_server::{
+        cors::CorsPolicies,
+        payload::PayloadPolicy,
+        routes::router::{self, RouterConfig, RouterState},
+    },
     services::{
         AggregatorChainDataImporter, CertificateChainSynchronizer, CertifierService,
         MessageService, MithrilSignerRegistrationFollower, ProverService, SignedEntityService,
@@ -465,6 +469,15 @@ impl DependenciesBuilder {
                 allow_http_serve_directory: self.configuration.allow_http_serve_directory(),
                 origin_tag_white_list: self.configuration.compute_origin_tag_white_list(),
                 aggregate_signature_type: self.configuration.aggregate_signature_type(),
+                signature_registration_rate_limit: self
+                    .configuration
+                    .get_signature_registration_rate_limit()?,
+                cors_policies: CorsPolicies::from_config(
+                    &self.configuration.cors_policy().unwrap_or_default(),
+                )?,
+                payload_policy: PayloadPolicy::from_config(
+                    &self.configuration.payload_policy().unwrap_or_default(),
+                ),
             },
         );
 
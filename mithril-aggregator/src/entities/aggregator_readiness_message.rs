@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Message structure of a single readiness check
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadinessCheckMessage {
+    /// Name of the checked dependency
+    pub name: String,
+
+    /// True if the dependency is ready
+    pub ready: bool,
+
+    /// Reason why the dependency is not ready
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Message structure of the aggregator readiness
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregatorReadinessMessage {
+    /// True if all the checks are ready
+    pub ready: bool,
+
+    /// Readiness checks of the aggregator dependencies
+    pub checks: Vec<ReadinessCheckMessage>,
+}
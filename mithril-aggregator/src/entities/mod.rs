@@ -3,12 +3,14 @@
 //! This module provide domain entities for the services & state machine.
 //!
 mod aggregator_epoch_settings;
+mod aggregator_readiness_message;
 mod leader_aggregator_epoch_settings;
 mod open_message;
 mod signer_registration_message;
 mod signer_ticker_message;
 
 pub use aggregator_epoch_settings::AggregatorEpochSettings;
+pub use aggregator_readiness_message::{AggregatorReadinessMessage, ReadinessCheckMessage};
 pub use leader_aggregator_epoch_settings::LeaderAggregatorEpochSettings;
 pub use open_message::OpenMessage;
 pub use signer_registration_message::{
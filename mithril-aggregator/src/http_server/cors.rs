@@ -0,0 +1,144 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use warp::cors::Cors;
+use warp::http::uri::{Authority, Scheme};
+use warp::http::{HeaderName, Method};
+
+use mithril_common::{
+    MITHRIL_API_VERSION_HEADER, MITHRIL_CLIENT_TYPE_HEADER, MITHRIL_ORIGIN_TAG_HEADER, StdResult,
+};
+
+use crate::configuration::CorsPolicyConfig;
+use crate::http_server::route_group::RouteGroup;
+
+/// CORS policies of the aggregator HTTP server, one per [group of routes][RouteGroup]
+#[derive(Debug, Clone)]
+pub struct CorsPolicies {
+    read: Cors,
+    registration: Cors,
+}
+
+impl CorsPolicies {
+    /// Check the given configuration and build the policies from it
+    pub fn from_config(config: &CorsPolicyConfig) -> StdResult<Self> {
+        for origin in config
+            .allowed_origins
+            .iter()
+            .chain(config.registration_allowed_origins.iter())
+            .flatten()
+        {
+            Self::check_origin(origin)?;
+        }
+        for header in &config.additional_allowed_headers {
+            HeaderName::from_str(header)
+                .with_context(|| format!("Invalid CORS allowed header '{header}'"))?;
+        }
+
+        Ok(Self::build(config))
+    }
+
+    /// Return the policy of the given group of routes
+    pub fn get(&self, group: RouteGroup) -> Cors {
+        match group {
+            RouteGroup::Read => self.read.clone(),
+            RouteGroup::Registration => self.registration.clone(),
+        }
+    }
+
+    /// Warp panics when given an origin that is not formatted as `scheme://host[:port]`
+    fn check_origin(origin: &str) -> StdResult<()> {
+        let (scheme, authority) = origin
+            .split_once("://")
+            .ok_or_else(|| anyhow!("Invalid CORS allowed origin '{origin}': missing scheme"))?;
+        Scheme::from_str(scheme)
+            .and_then(|_| Authority::from_str(authority))
+            .map(|_| ())
+            .with_context(|| {
+                format!("Invalid CORS allowed origin '{origin}': expected 'scheme://host[:port]'")
+            })
+    }
+
+    fn build(config: &CorsPolicyConfig) -> Self {
+        let registration_allowed_origins = config
+            .registration_allowed_origins
+            .as_ref()
+            .or(config.allowed_origins.as_ref());
+
+        Self {
+            read: Self::build_cors(config, config.allowed_origins.as_ref()),
+            registration: Self::build_cors(config, registration_allowed_origins),
+        }
+    }
+
+    fn build_cors(config: &CorsPolicyConfig, allowed_origins: Option<&Vec<String>>) -> Cors {
+        let cors = warp::cors()
+            .allow_headers(
+                [
+                    "content-type",
+                    MITHRIL_API_VERSION_HEADER,
+                    MITHRIL_ORIGIN_TAG_HEADER,
+                    MITHRIL_CLIENT_TYPE_HEADER,
+                ]
+                .into_iter()
+                .chain(config.additional_allowed_headers.iter().map(String::as_str)),
+            )
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+        let cors = match allowed_origins {
+            Some(origins) => cors.allow_origins(origins.iter().map(String::as_str)),
+            None => cors.allow_any_origin(),
+        };
+        let cors = match config.max_age_in_seconds {
+            Some(max_age) => cors.max_age(Duration::from_secs(max_age)),
+            None => cors,
+        };
+
+        cors.build()
+    }
+}
+
+impl Default for CorsPolicies {
+    fn default() -> Self {
+        Self::build(&CorsPolicyConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_config_accepts_valid_origins_and_headers() {
+        CorsPolicies::from_config(&CorsPolicyConfig {
+            allowed_origins: Some(vec![
+                "https://mithril.network".to_string(),
+                "http://localhost:3000".to_string(),
+            ]),
+            registration_allowed_origins: Some(vec![]),
+            additional_allowed_headers: vec!["x-custom-header".to_string()],
+            max_age_in_seconds: Some(600),
+        })
+        .expect("CORS policies should be built");
+    }
+
+    #[test]
+    fn from_config_fails_with_invalid_origin() {
+        for origin in ["mithril.network", "https://mithril.network/path", "https://"] {
+            CorsPolicies::from_config(&CorsPolicyConfig {
+                registration_allowed_origins: Some(vec![origin.to_string()]),
+                ..CorsPolicyConfig::default()
+            })
+            .expect_err(&format!("Origin '{origin}' should be rejected"));
+        }
+    }
+
+    #[test]
+    fn from_config_fails_with_invalid_header() {
+        CorsPolicies::from_config(&CorsPolicyConfig {
+            additional_allowed_headers: vec!["invalid header".to_string()],
+            ..CorsPolicyConfig::default()
+        })
+        .expect_err("Header should be rejected");
+    }
+}
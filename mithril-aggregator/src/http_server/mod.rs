@@ -1,5 +1,9 @@
+pub mod cors;
 pub mod parameters;
+pub mod payload;
+pub mod route_group;
 pub mod routes;
+pub mod tls;
 pub mod validators;
 
 pub const SERVER_BASE_PATH: &str = "aggregator";
@@ -0,0 +1,448 @@
+use std::io::Read;
+
+use flate2::read::{GzDecoder, ZlibDecoder};
+use serde::de::DeserializeOwned;
+use warp::http::StatusCode;
+use warp::path::Peek;
+use warp::{Filter, Rejection, Reply};
+
+use mithril_common::entities::ClientError;
+
+use crate::configuration::PayloadPolicyConfig;
+use crate::http_server::route_group::RouteGroup;
+use crate::http_server::routes::reply;
+use crate::http_server::{
+    CARDANO_DATABASE_DOWNLOAD_PATH, SERVER_BASE_PATH, SNAPSHOT_DOWNLOAD_PATH,
+};
+
+/// Default maximum size of the request bodies, 256 KiB
+pub const DEFAULT_MAX_BODY_SIZE: u64 = 1024 * 256;
+
+/// Routes serving the artifacts archives, which are already compressed
+const FILE_DOWNLOAD_ROUTES: [&str; 2] = [SNAPSHOT_DOWNLOAD_PATH, CARDANO_DATABASE_DOWNLOAD_PATH];
+
+/// Payload policy of the aggregator HTTP server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadPolicy {
+    max_body_size: u64,
+    registration_max_body_size: u64,
+    request_decompression: bool,
+    response_compression: bool,
+}
+
+impl PayloadPolicy {
+    /// Build the policy from the given configuration
+    pub fn from_config(config: &PayloadPolicyConfig) -> Self {
+        let max_body_size = config.max_body_size_in_bytes.unwrap_or(DEFAULT_MAX_BODY_SIZE);
+
+        Self {
+            max_body_size,
+            registration_max_body_size: config
+                .registration_max_body_size_in_bytes
+                .unwrap_or(max_body_size),
+            request_decompression: config.request_decompression,
+            response_compression: config.response_compression,
+        }
+    }
+
+    /// Maximum size of the request bodies of the given group of routes
+    pub fn max_body_size(&self, group: RouteGroup) -> u64 {
+        match group {
+            RouteGroup::Read => self.max_body_size,
+            RouteGroup::Registration => self.registration_max_body_size,
+        }
+    }
+
+    /// Whether compressed request bodies are accepted
+    pub fn request_decompression(&self) -> bool {
+        self.request_decompression
+    }
+
+    /// Whether the responses of the [read routes][RouteGroup::Read] are compressed
+    pub fn response_compression(&self) -> bool {
+        self.response_compression
+    }
+}
+
+impl Default for PayloadPolicy {
+    fn default() -> Self {
+        Self::from_config(&PayloadPolicyConfig::default())
+    }
+}
+
+/// Content encodings supported for the request bodies and the responses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    /// Gzip encoding
+    Gzip,
+    /// Deflate encoding (zlib format)
+    Deflate,
+}
+
+impl ContentEncoding {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Self::Gzip),
+            "deflate" => Some(Self::Deflate),
+            _ => None,
+        }
+    }
+
+    /// Select the encoding of a response from the `Accept-Encoding` header of the request,
+    /// gzip is preferred over deflate
+    fn negotiate(accept_encoding: &str) -> Option<Self> {
+        let accepted: Vec<Self> = accept_encoding
+            .split(',')
+            .filter_map(|item| {
+                let mut parameters = item.split(';');
+                let encoding = Self::parse(parameters.next().unwrap_or_default())?;
+                let is_refused = parameters.any(|parameter| {
+                    parameter
+                        .trim()
+                        .strip_prefix("q=")
+                        .and_then(|quality| quality.parse::<f32>().ok())
+                        == Some(0.0)
+                });
+
+                (!is_refused).then_some(encoding)
+            })
+            .collect();
+
+        [Self::Gzip, Self::Deflate]
+            .into_iter()
+            .find(|encoding| accepted.contains(encoding))
+    }
+
+    fn decode(self, body: &[u8], max_length: u64) -> Result<Vec<u8>, InvalidRequestBody> {
+        let decoder: Box<dyn Read + '_> = match self {
+            Self::Gzip => Box::new(GzDecoder::new(body)),
+            Self::Deflate => Box::new(ZlibDecoder::new(body)),
+        };
+        let mut decoded = Vec::new();
+        // Read one more byte than allowed to detect the bodies that are too large
+        decoder
+            .take(max_length + 1)
+            .read_to_end(&mut decoded)
+            .map_err(|error| {
+                InvalidRequestBody::new(
+                    StatusCode::BAD_REQUEST,
+                    format!("Could not decompress the request body: {error}"),
+                )
+            })?;
+
+        if decoded.len() as u64 > max_length {
+            return Err(InvalidRequestBody::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("The decompressed request body is larger than {max_length} bytes"),
+            ));
+        }
+
+        Ok(decoded)
+    }
+}
+
+/// Rejection of a request whose body could not be decoded
+#[derive(Debug)]
+pub struct InvalidRequestBody {
+    status_code: StatusCode,
+    message: String,
+}
+
+impl warp::reject::Reject for InvalidRequestBody {}
+
+impl InvalidRequestBody {
+    fn new<T: Into<String>>(status_code: StatusCode, message: T) -> Self {
+        Self {
+            status_code,
+            message: message.into(),
+        }
+    }
+
+    /// Rejection of a request whose body is larger than the size limit of its route
+    pub fn payload_too_large() -> Self {
+        Self::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "The request body is larger than the size limit of the route",
+        )
+    }
+
+    /// Reply sent to the client for this rejection
+    pub fn to_reply(&self) -> Box<dyn Reply> {
+        reply::json(
+            &ClientError::new("Invalid request body".to_string(), self.message.clone()),
+            self.status_code,
+        )
+    }
+}
+
+/// Return the encoding of the request body, `None` if it is not encoded
+fn request_encoding(content_encoding: Option<&str>) -> Option<&str> {
+    content_encoding.filter(|encoding| !encoding.trim().eq_ignore_ascii_case("identity"))
+}
+
+/// Filter that only lets through the requests which body is not encoded
+pub fn without_request_encoding() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<String>("content-encoding")
+        .and_then(|content_encoding: Option<String>| async move {
+            match request_encoding(content_encoding.as_deref()) {
+                None => Ok(()),
+                Some(_) => Err(warp::reject::not_found()),
+            }
+        })
+        .untuple_one()
+}
+
+/// Extract the encoding of the request body, only lets through the requests which body is
+/// encoded and rejects the encodings that are not supported
+pub fn with_request_encoding(
+    decompression_enabled: bool,
+) -> impl Filter<Extract = (ContentEncoding,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("content-encoding").and_then(
+        move |content_encoding: Option<String>| async move {
+            let Some(encoding) = request_encoding(content_encoding.as_deref()) else {
+                return Err(warp::reject::not_found());
+            };
+
+            match ContentEncoding::parse(encoding) {
+                Some(content_encoding) if decompression_enabled => Ok(content_encoding),
+                _ => Err(warp::reject::custom(InvalidRequestBody::new(
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    format!("Unsupported request body encoding '{encoding}'"),
+                ))),
+            }
+        },
+    )
+}
+
+/// Decompress the given request body and deserialize it
+pub fn decode_json_body<T: DeserializeOwned>(
+    encoding: ContentEncoding,
+    body: &[u8],
+    max_length: u64,
+) -> Result<T, InvalidRequestBody> {
+    let decoded = encoding.decode(body, max_length)?;
+
+    serde_json::from_slice(&decoded).map_err(|error| {
+        InvalidRequestBody::new(
+            StatusCode::BAD_REQUEST,
+            format!("Could not deserialize the request body: {error}"),
+        )
+    })
+}
+
+/// Check if the given path targets a route serving the artifacts archives
+fn is_file_download_path<'a>(mut segments: impl Iterator<Item = &'a str>) -> bool {
+    matches!(
+        (segments.next(), segments.next()),
+        (Some(SERVER_BASE_PATH), Some(route)) if FILE_DOWNLOAD_ROUTES.contains(&route)
+    )
+}
+
+/// Filter that only lets through the requests which response must be compressed with the
+/// given encoding, `None` meaning that the response is not compressed
+///
+/// The archives downloads are never compressed again: it would be costly for files of several
+/// GB and their response would lose its `Content-Length` header.
+pub fn with_response_encoding(
+    expected: Option<ContentEncoding>,
+    compression_enabled: bool,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::path::peek()
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .and_then(
+            move |path: Peek, accept_encoding: Option<String>| async move {
+                let encoding = accept_encoding
+                    .as_deref()
+                    .filter(|_| compression_enabled && !is_file_download_path(path.segments()))
+                    .and_then(ContentEncoding::negotiate);
+
+                if encoding == expected {
+                    Ok(())
+                } else {
+                    Err(warp::reject::not_found())
+                }
+            },
+        )
+        .untuple_one()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+
+    use super::*;
+
+    fn encode(encoding: ContentEncoding, data: &[u8]) -> Vec<u8> {
+        match encoding {
+            ContentEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            }
+            ContentEncoding::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data).unwrap();
+                encoder.finish().unwrap()
+            }
+        }
+    }
+
+    #[test]
+    fn registration_max_body_size_fallback_to_max_body_size() {
+        let policy = PayloadPolicy::default();
+        assert_eq!(
+            DEFAULT_MAX_BODY_SIZE,
+            policy.max_body_size(RouteGroup::Read)
+        );
+        assert_eq!(
+            DEFAULT_MAX_BODY_SIZE,
+            policy.max_body_size(RouteGroup::Registration)
+        );
+
+        let policy = PayloadPolicy::from_config(&PayloadPolicyConfig {
+            max_body_size_in_bytes: Some(100),
+            ..PayloadPolicyConfig::default()
+        });
+        assert_eq!(100, policy.max_body_size(RouteGroup::Read));
+        assert_eq!(100, policy.max_body_size(RouteGroup::Registration));
+
+        let policy = PayloadPolicy::from_config(&PayloadPolicyConfig {
+            max_body_size_in_bytes: Some(100),
+            registration_max_body_size_in_bytes: Some(1000),
+            ..PayloadPolicyConfig::default()
+        });
+        assert_eq!(100, policy.max_body_size(RouteGroup::Read));
+        assert_eq!(1000, policy.max_body_size(RouteGroup::Registration));
+    }
+
+    #[test]
+    fn negotiate_response_encoding() {
+        assert_eq!(None, ContentEncoding::negotiate(""));
+        assert_eq!(None, ContentEncoding::negotiate("br, identity"));
+        assert_eq!(
+            Some(ContentEncoding::Gzip),
+            ContentEncoding::negotiate("deflate, gzip;q=0.8, br")
+        );
+        assert_eq!(
+            Some(ContentEncoding::Deflate),
+            ContentEncoding::negotiate("gzip;q=0, deflate")
+        );
+        assert_eq!(None, ContentEncoding::negotiate("GZIP; q=0, deflate;q=0.0"));
+    }
+
+    #[test]
+    fn decode_json_body_with_supported_encodings() {
+        let json = br#"{"key":"value"}"#;
+
+        for encoding in [ContentEncoding::Gzip, ContentEncoding::Deflate] {
+            let value: serde_json::Value =
+                decode_json_body(encoding, &encode(encoding, json), json.len() as u64).unwrap();
+
+            assert_eq!(serde_json::json!({"key": "value"}), value);
+        }
+    }
+
+    #[test]
+    fn decode_json_body_fails_when_decompressed_body_is_too_large() {
+        let json = br#"{"key":"value"}"#;
+
+        let error = decode_json_body::<serde_json::Value>(
+            ContentEncoding::Gzip,
+            &encode(ContentEncoding::Gzip, json),
+            json.len() as u64 - 1,
+        )
+        .unwrap_err();
+
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, error.status_code);
+    }
+
+    #[test]
+    fn decode_json_body_fails_with_invalid_body() {
+        let error = decode_json_body::<serde_json::Value>(
+            ContentEncoding::Gzip,
+            b"not gzip encoded",
+            DEFAULT_MAX_BODY_SIZE,
+        )
+        .unwrap_err();
+        assert_eq!(StatusCode::BAD_REQUEST, error.status_code);
+
+        let error = decode_json_body::<serde_json::Value>(
+            ContentEncoding::Deflate,
+            &encode(ContentEncoding::Deflate, b"not json"),
+            DEFAULT_MAX_BODY_SIZE,
+        )
+        .unwrap_err();
+        assert_eq!(StatusCode::BAD_REQUEST, error.status_code);
+    }
+
+    #[tokio::test]
+    async fn request_encoding_filters() {
+        let request = |content_encoding: Option<&str>| {
+            let request = warp::test::request();
+            match content_encoding {
+                Some(value) => request.header("content-encoding", value),
+                None => request,
+            }
+        };
+
+        assert!(request(None).matches(&without_request_encoding()).await);
+        assert!(request(Some("identity")).matches(&without_request_encoding()).await);
+        assert!(!request(Some("gzip")).matches(&without_request_encoding()).await);
+
+        assert_eq!(
+            ContentEncoding::Deflate,
+            request(Some("deflate"))
+                .filter(&with_request_encoding(true))
+                .await
+                .unwrap()
+        );
+        assert!(!request(None).matches(&with_request_encoding(true)).await);
+        assert!(!request(Some("br")).matches(&with_request_encoding(true)).await);
+        assert!(!request(Some("gzip")).matches(&with_request_encoding(false)).await);
+    }
+
+    #[tokio::test]
+    async fn response_encoding_filter() {
+        let request = || warp::test::request().header("accept-encoding", "deflate");
+
+        assert!(
+            request()
+                .matches(&with_response_encoding(
+                    Some(ContentEncoding::Deflate),
+                    true
+                ))
+                .await
+        );
+        assert!(!request().matches(&with_response_encoding(None, true)).await);
+        assert!(request().matches(&with_response_encoding(None, false)).await);
+        assert!(
+            warp::test::request()
+                .matches(&with_response_encoding(None, true))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn response_encoding_filter_never_compress_archives_downloads() {
+        for path in [
+            "/aggregator/snapshot_download/snapshot.tar.zst",
+            "/aggregator/cardano-database-download/immutable/00001.tar.zst",
+        ] {
+            let request = || {
+                warp::test::request()
+                    .path(path)
+                    .header("accept-encoding", "gzip, deflate")
+            };
+
+            assert!(request().matches(&with_response_encoding(None, true)).await);
+            assert!(
+                !request()
+                    .matches(&with_response_encoding(Some(ContentEncoding::Gzip), true))
+                    .await
+            );
+        }
+    }
+}
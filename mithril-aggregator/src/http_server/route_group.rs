@@ -0,0 +1,66 @@
+use warp::path::Peek;
+use warp::{Filter, Rejection};
+
+use crate::http_server::SERVER_BASE_PATH;
+
+/// Routes receiving the registrations of the signers
+const REGISTRATION_ROUTES: [&str; 2] = ["register-signer", "register-signatures"];
+
+/// Group of routes sharing the same CORS and payload policies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteGroup {
+    /// Routes used by the clients of the aggregator
+    Read,
+    /// Routes receiving the registrations of the signers
+    Registration,
+}
+
+impl RouteGroup {
+    fn from_path<'a>(mut segments: impl Iterator<Item = &'a str>) -> Self {
+        match (segments.next(), segments.next()) {
+            (Some(SERVER_BASE_PATH), Some(route)) if REGISTRATION_ROUTES.contains(&route) => {
+                Self::Registration
+            }
+            _ => Self::Read,
+        }
+    }
+}
+
+/// Filter that only lets through the requests targeting a route of the given group
+pub fn with_route_group(group: RouteGroup) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::path::peek()
+        .and_then(move |path: Peek| async move {
+            if RouteGroup::from_path(path.segments()) == group {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            }
+        })
+        .untuple_one()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_group_from_path() {
+        assert_eq!(
+            RouteGroup::Registration,
+            RouteGroup::from_path(["aggregator", "register-signer"].into_iter())
+        );
+        assert_eq!(
+            RouteGroup::Registration,
+            RouteGroup::from_path(["aggregator", "register-signatures"].into_iter())
+        );
+        assert_eq!(
+            RouteGroup::Read,
+            RouteGroup::from_path(["aggregator", "signers", "tickers"].into_iter())
+        );
+        assert_eq!(
+            RouteGroup::Read,
+            RouteGroup::from_path(["register-signer"].into_iter())
+        );
+        assert_eq!(RouteGroup::Read, RouteGroup::from_path([].into_iter()));
+    }
+}
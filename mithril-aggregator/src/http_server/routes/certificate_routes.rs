@@ -1,8 +1,15 @@
+use serde::{Deserialize, Serialize};
 use warp::Filter;
 
 use crate::http_server::routes::middlewares;
 use crate::http_server::routes::router::RouterState;
 
+#[derive(Deserialize, Serialize, Debug, Default)]
+struct CertificateListQueryParams {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
 pub fn routes(
     router_state: &RouterState,
 ) -> impl Filter<Extract = (impl warp::Reply + use<>,), Error = warp::Rejection> + Clone + use<> {
@@ -17,7 +24,11 @@ fn certificate_certificates(
 ) -> impl Filter<Extract = (impl warp::Reply + use<>,), Error = warp::Rejection> + Clone + use<> {
     warp::path!("certificates")
         .and(warp::get())
+        .and(warp::query::<CertificateListQueryParams>())
         .and(middlewares::with_logger(router_state))
+        .and(middlewares::extract_config(router_state, |config| {
+            config.server_url.clone()
+        }))
         .and(middlewares::with_http_message_service(router_state))
         .and_then(handlers::certificate_certificates)
 }
@@ -54,27 +65,72 @@ mod handlers {
     use std::sync::Arc;
     use warp::http::StatusCode;
 
+    use mithril_common::StdResult;
+
     use crate::MetricsService;
     use crate::http_server::routes::middlewares::ClientMetadata;
+    use crate::tools::url_sanitizer::SanitizedUrlWithTrailingSlash;
+    use crate::unwrap_to_internal_server_error;
     use crate::{http_server::routes::reply, services::MessageService};
 
+    use super::CertificateListQueryParams;
+
     pub const LIST_MAX_ITEMS: usize = 20;
 
-    /// List all Certificates
+    /// List the Certificates, from the latest to the oldest, by pages of at most
+    /// [LIST_MAX_ITEMS] certificates
     pub async fn certificate_certificates(
+        query: CertificateListQueryParams,
         logger: Logger,
+        server_url: SanitizedUrlWithTrailingSlash,
         http_message_service: Arc<dyn MessageService>,
     ) -> Result<impl warp::Reply, Infallible> {
-        match http_message_service
-            .get_certificate_list_message(LIST_MAX_ITEMS)
-            .await
-        {
-            Ok(certificates) => Ok(reply::json(&certificates, StatusCode::OK)),
-            Err(err) => {
-                warn!(logger,"certificate_certificates::error"; "error" => ?err);
-                Ok(reply::server_error(err))
+        let offset = query.offset.unwrap_or_default();
+        let limit = query.limit.unwrap_or(LIST_MAX_ITEMS).clamp(1, LIST_MAX_ITEMS);
+
+        // Fetch one more certificate to know if there is a next page
+        let mut certificates = unwrap_to_internal_server_error!(
+            http_message_service.get_certificate_list_message(offset, limit + 1).await,
+            logger => "certificate_certificates::error"
+        );
+        let has_next_page = certificates.len() > limit;
+        certificates.truncate(limit);
+        let links = unwrap_to_internal_server_error!(
+            pagination_links(&server_url, offset, limit, has_next_page),
+            logger => "certificate_certificates::error"
+        );
+
+        let reply = reply::json(&certificates, StatusCode::OK);
+        match links {
+            Some(links) => {
+                Ok(Box::new(warp::reply::with_header(reply, "link", links))
+                    as Box<dyn warp::Reply>)
             }
+            None => Ok(reply),
+        }
+    }
+
+    /// Compute the value of the `Link` header pointing to the previous and next pages, if any
+    fn pagination_links(
+        server_url: &SanitizedUrlWithTrailingSlash,
+        offset: usize,
+        limit: usize,
+        has_next_page: bool,
+    ) -> StdResult<Option<String>> {
+        let page_link = |offset: usize, rel: &str| -> StdResult<String> {
+            let url = server_url.join(&format!("certificates?offset={offset}&limit={limit}"))?;
+            Ok(format!("<{url}>; rel=\"{rel}\""))
+        };
+
+        let mut links = vec![];
+        if offset > 0 {
+            links.push(page_link(offset.saturating_sub(limit), "prev")?);
+        }
+        if has_next_page {
+            links.push(page_link(offset + limit, "next")?);
         }
+
+        Ok((!links.is_empty()).then(|| links.join(", ")))
     }
 
     /// Certificate by certificate hash
@@ -139,7 +195,8 @@ mod tests {
 
     use mithril_api_spec::APISpec;
     use mithril_common::{
-        MITHRIL_CLIENT_TYPE_HEADER, MITHRIL_ORIGIN_TAG_HEADER, messages::CertificateMessage,
+        MITHRIL_CLIENT_TYPE_HEADER, MITHRIL_ORIGIN_TAG_HEADER,
+        messages::{CertificateListMessage, CertificateMessage},
         test::double::fake_data,
     };
 
@@ -190,13 +247,55 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_certificate_certificates_get_pages_with_link_header() {
+        let dependency_manager = initialize_dependencies!().await;
+        for hash in ["certificate-1", "certificate-2", "certificate-3"] {
+            dependency_manager
+                .certificate_repository
+                .create_certificate(fake_data::genesis_certificate(hash))
+                .await
+                .expect("certificate store save should have succeeded");
+        }
+        let router = setup_router(RouterState::new_with_dummy_config(Arc::new(
+            dependency_manager,
+        )));
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path("/certificates?offset=1&limit=1")
+            .reply(&router)
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        let certificates: CertificateListMessage = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(
+            vec!["certificate-2".to_string()],
+            certificates.into_iter().map(|c| c.hash).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "<http://0.0.0.0:8000/certificates?offset=0&limit=1>; rel=\"prev\", \
+            <http://0.0.0.0:8000/certificates?offset=2&limit=1>; rel=\"next\"",
+            response.headers().get("link").unwrap()
+        );
+
+        let response = request()
+            .method(Method::GET.as_str())
+            .path("/certificates")
+            .reply(&router)
+            .await;
+
+        assert_eq!(StatusCode::OK, response.status());
+        assert!(response.headers().get("link").is_none());
+    }
+
     #[tokio::test]
     async fn test_certificate_when_error_retrieving_certificates_returns_ko_500() {
         let mut dependency_manager = initialize_dependencies!().await;
         let mut message_service = MockMessageService::new();
         message_service
             .expect_get_certificate_list_message()
-            .returning(|_| Err(anyhow!("an error")));
+            .returning(|_, _| Err(anyhow!("an error")));
         dependency_manager.message_service = Arc::new(message_service);
 
         let method = Method::GET.as_str();
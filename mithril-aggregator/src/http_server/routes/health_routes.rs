@@ -0,0 +1,295 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use warp::Filter;
+
+use crate::http_server::routes::{middlewares, router::RouterState};
+
+pub fn routes(
+    router_state: &RouterState,
+) -> impl Filter<Extract = (impl warp::Reply + use<>,), Error = warp::Rejection> + Clone + use<> {
+    liveness().or(readiness(router_state))
+}
+
+/// GET /health/live
+fn liveness()
+-> impl Filter<Extract = (impl warp::Reply + use<>,), Error = warp::Rejection> + Clone + use<> {
+    warp::path!("health" / "live")
+        .and(warp::get())
+        .and_then(handlers::liveness)
+}
+
+/// GET /health/ready
+fn readiness(
+    router_state: &RouterState,
+) -> impl Filter<Extract = (impl warp::Reply + use<>,), Error = warp::Rejection> + Clone + use<> {
+    // Shared by all the requests: set once the chain observer has answered
+    let chain_observer_ready = Arc::new(AtomicBool::new(false));
+
+    warp::path!("health" / "ready")
+        .and(warp::get())
+        .and(middlewares::with_logger(router_state))
+        .and(warp::any().map(move || chain_observer_ready.clone()))
+        .and(middlewares::with_ticker_service(router_state))
+        .and(middlewares::with_epoch_service(router_state))
+        .and_then(handlers::readiness)
+}
+
+mod handlers {
+    use slog::{Logger, debug};
+    use std::convert::Infallible;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use warp::http::StatusCode;
+
+    use mithril_common::StdResult;
+    use mithril_ticker::TickerService;
+
+    use crate::dependency_injection::EpochServiceWrapper;
+    use crate::entities::{AggregatorReadinessMessage, ReadinessCheckMessage};
+    use crate::http_server::routes::reply;
+
+    fn readiness_check(name: &str, result: StdResult<()>) -> ReadinessCheckMessage {
+        ReadinessCheckMessage {
+            name: name.to_string(),
+            ready: result.is_ok(),
+            error: result.err().map(|error| format!("{error:#}")),
+        }
+    }
+
+    /// Liveness
+    pub async fn liveness() -> Result<impl warp::Reply, Infallible> {
+        Ok(reply::empty(StatusCode::OK))
+    }
+
+    /// Check that the chain observer has answered once, it is no longer queried afterward
+    async fn check_chain_observer(
+        chain_observer_ready: &AtomicBool,
+        ticker_service: &dyn TickerService,
+    ) -> StdResult<()> {
+        if chain_observer_ready.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        ticker_service.get_current_time_point().await?;
+        chain_observer_ready.store(true, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Readiness
+    ///
+    /// The database migrations are applied before the HTTP server starts, so only the
+    /// dependencies that are initialized at runtime are checked.
+    ///
+    /// The Cardano chain observer is queried until its first success only: probes must not
+    /// spawn a query to the Cardano node each time, nor fail on its transient errors.
+    pub async fn readiness(
+        logger: Logger,
+        chain_observer_ready: Arc<AtomicBool>,
+        ticker_service: Arc<dyn TickerService>,
+        epoch_service: EpochServiceWrapper,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let checks = vec![
+            readiness_check(
+                "cardano_chain_observer",
+                check_chain_observer(&chain_observer_ready, ticker_service.as_ref()).await,
+            ),
+            readiness_check(
+                "epoch_service",
+                epoch_service.read().await.epoch_of_current_data().map(|_| ()),
+            ),
+        ];
+        let message = AggregatorReadinessMessage {
+            ready: checks.iter().all(|check| check.ready),
+            checks,
+        };
+
+        if message.ready {
+            Ok(reply::json(&message, StatusCode::OK))
+        } else {
+            debug!(logger, "health_readiness::not_ready"; "checks" => ?message.checks);
+            Ok(reply::json(&message, StatusCode::SERVICE_UNAVAILABLE))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value::Null;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+    use warp::{
+        http::{Method, StatusCode},
+        test::request,
+    };
+
+    use mithril_api_spec::APISpec;
+    use mithril_cardano_node_chain::test::double::FakeChainObserver;
+    use mithril_cardano_node_internal_database::test::double::DumbImmutableFileObserver;
+    use mithril_common::entities::{Epoch, TimePoint};
+    use mithril_common::test::{builder::MithrilFixtureBuilder, double::Dummy};
+    use mithril_ticker::MithrilTickerService;
+
+    use crate::entities::AggregatorReadinessMessage;
+    use crate::initialize_dependencies;
+    use crate::services::FakeEpochService;
+
+    use super::*;
+
+    fn setup_router(
+        state: RouterState,
+    ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+        let cors = warp::cors()
+            .allow_any_origin()
+            .allow_headers(vec!["content-type"])
+            .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
+
+        warp::any().and(routes(&state).with(cors))
+    }
+
+    async fn ticker_service(time_point: Option<TimePoint>) -> Arc<MithrilTickerService> {
+        let immutable_file_observer = Arc::new(DumbImmutableFileObserver::default());
+        immutable_file_observer
+            .shall_return(time_point.as_ref().map(|t| t.immutable_file_number))
+            .await;
+
+        Arc::new(MithrilTickerService::new(
+            Arc::new(FakeChainObserver::new(time_point)),
+            immutable_file_observer,
+        ))
+    }
+
+    #[tokio::test]
+    async fn liveness_route_ok_200() {
+        let dependency_manager = initialize_dependencies!().await;
+        let method = Method::GET.as_str();
+        let path = "/health/live";
+
+        let response = request()
+            .method(method)
+            .path(path)
+            .reply(&setup_router(RouterState::new_with_dummy_config(Arc::new(
+                dependency_manager,
+            ))))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_default_spec_file_from(crate::http_server::API_SPEC_LOCATION),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn readiness_route_ok_200_when_all_checks_are_ready() {
+        let mut dependency_manager = initialize_dependencies!().await;
+        let fixture = MithrilFixtureBuilder::default().build();
+        dependency_manager.epoch_service = Arc::new(RwLock::new(FakeEpochService::from_fixture(
+            Epoch(5),
+            &fixture,
+        )));
+        dependency_manager.ticker_service = ticker_service(Some(TimePoint::dummy())).await;
+
+        let method = Method::GET.as_str();
+        let path = "/health/ready";
+
+        let response = request()
+            .method(method)
+            .path(path)
+            .reply(&setup_router(RouterState::new_with_dummy_config(Arc::new(
+                dependency_manager,
+            ))))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_default_spec_file_from(crate::http_server::API_SPEC_LOCATION),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::OK,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn readiness_route_does_not_query_the_chain_observer_after_its_first_success() {
+        let mut dependency_manager = initialize_dependencies!().await;
+        let fixture = MithrilFixtureBuilder::default().build();
+        dependency_manager.epoch_service = Arc::new(RwLock::new(FakeEpochService::from_fixture(
+            Epoch(5),
+            &fixture,
+        )));
+        let chain_observer = Arc::new(FakeChainObserver::new(Some(TimePoint::dummy())));
+        let immutable_file_observer = Arc::new(DumbImmutableFileObserver::default());
+        dependency_manager.ticker_service = Arc::new(MithrilTickerService::new(
+            chain_observer.clone(),
+            immutable_file_observer,
+        ));
+        let router = setup_router(RouterState::new_with_dummy_config(Arc::new(
+            dependency_manager,
+        )));
+        let get_readiness_status = || async {
+            request()
+                .method(Method::GET.as_str())
+                .path("/health/ready")
+                .reply(&router)
+                .await
+                .status()
+        };
+
+        assert_eq!(StatusCode::OK, get_readiness_status().await);
+
+        chain_observer.set_current_time_point(None).await;
+        assert_eq!(StatusCode::OK, get_readiness_status().await);
+    }
+
+    #[tokio::test]
+    async fn readiness_route_ko_503_with_the_failed_checks() {
+        let mut dependency_manager = initialize_dependencies!().await;
+        dependency_manager.ticker_service = ticker_service(None).await;
+
+        let method = Method::GET.as_str();
+        let path = "/health/ready";
+
+        let response = request()
+            .method(method)
+            .path(path)
+            .reply(&setup_router(RouterState::new_with_dummy_config(Arc::new(
+                dependency_manager,
+            ))))
+            .await;
+
+        APISpec::verify_conformity(
+            APISpec::get_default_spec_file_from(crate::http_server::API_SPEC_LOCATION),
+            method,
+            path,
+            "application/json",
+            &Null,
+            &response,
+            &StatusCode::SERVICE_UNAVAILABLE,
+        )
+        .unwrap();
+
+        let message: AggregatorReadinessMessage = serde_json::from_slice(response.body()).unwrap();
+        assert!(!message.ready);
+        assert_eq!(
+            vec![
+                ("cardano_chain_observer".to_string(), false),
+                ("epoch_service".to_string(), false)
+            ],
+            message
+                .checks
+                .into_iter()
+                .map(|check| (check.name, check.ready))
+                .collect::<Vec<_>>()
+        );
+    }
+}
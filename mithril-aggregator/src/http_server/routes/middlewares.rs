@@ -6,10 +6,13 @@ use warp::{Filter, Rejection};
 
 use mithril_common::api_version::APIVersionProvider;
 use mithril_common::{MITHRIL_CLIENT_TYPE_HEADER, MITHRIL_ORIGIN_TAG_HEADER};
+use mithril_ticker::TickerService;
 
 use crate::database::repository::SignerGetter;
 use crate::dependency_injection::EpochServiceWrapper;
 use crate::event_store::{EventMessage, TransmitterService};
+use crate::http_server::payload;
+use crate::http_server::route_group::RouteGroup;
 use crate::http_server::routes::http_server_child_logger;
 use crate::http_server::routes::router::{RouterConfig, RouterState};
 use crate::services::{CertifierService, MessageService, ProverService, SignedEntityService};
@@ -24,6 +27,28 @@ pub(crate) fn json_with_max_length<T: DeserializeOwned + Send>(
     warp::body::content_length_limit(max_length).and(warp::body::json())
 }
 
+/// Extract a value from the body, following the payload policy of the given group of routes
+pub(crate) fn json_body<T: DeserializeOwned + Send>(
+    router_state: &RouterState,
+    group: RouteGroup,
+) -> impl Filter<Extract = (T,), Error = Rejection> + Clone + use<T> {
+    let payload_policy = &router_state.configuration.payload_policy;
+    let max_length = payload_policy.max_body_size(group);
+
+    payload::without_request_encoding()
+        .and(json_with_max_length(max_length))
+        .or(
+            payload::with_request_encoding(payload_policy.request_decompression())
+                .and(warp::body::content_length_limit(max_length))
+                .and(warp::body::bytes())
+                .and_then(move |encoding, body: warp::hyper::body::Bytes| async move {
+                    payload::decode_json_body(encoding, &body, max_length)
+                        .map_err(warp::reject::custom)
+                }),
+        )
+        .unify()
+}
+
 /// Extract a value from the configuration
 pub(crate) fn extract_config<D: Clone + Send>(
     state: &RouterState,
@@ -100,6 +125,14 @@ pub fn with_epoch_service(
     warp::any().map(move || epoch_service.clone())
 }
 
+/// With ticker service middleware
+pub fn with_ticker_service(
+    router_state: &RouterState,
+) -> impl Filter<Extract = (Arc<dyn TickerService>,), Error = Infallible> + Clone + use<> {
+    let ticker_service = router_state.dependencies.ticker_service.clone();
+    warp::any().map(move || ticker_service.clone())
+}
+
 /// With signed entity service
 pub fn with_signed_entity_service(
     router_state: &RouterState,
@@ -198,7 +231,9 @@ pub struct ClientMetadata {
 }
 
 pub mod validators {
-    use crate::http_server::validators::ProverTransactionsHashValidator;
+    use crate::http_server::validators::{
+        ProverTransactionsHashValidator, SignatureRegistrationRateLimiter,
+    };
 
     use super::*;
 
@@ -213,6 +248,21 @@ pub mod validators {
 
         warp::any().map(move || ProverTransactionsHashValidator::new(max_hashes))
     }
+
+    /// With Signature Registration Rate Limiter
+    ///
+    /// The rate limiter is shared by all the requests handled by the returned filter.
+    pub fn with_signature_registration_rate_limiter(
+        router_state: &RouterState,
+    ) -> impl Filter<Extract = (Arc<SignatureRegistrationRateLimiter>,), Error = Infallible>
+    + Clone
+    + use<> {
+        let rate_limiter = Arc::new(SignatureRegistrationRateLimiter::new(
+            router_state.configuration.signature_registration_rate_limit,
+        ));
+
+        warp::any().map(move || rate_limiter.clone())
+    }
 }
 
 #[cfg(test)]
@@ -439,4 +489,148 @@ mod tests {
             assert_eq!(response.status(), StatusCode::LENGTH_REQUIRED);
         }
     }
+
+    mod json_body {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        use mithril_api_spec::APISpec;
+        use mithril_common::messages::{RegisterSignatureMessageHttp, RegisterSignerMessage};
+
+        use crate::configuration::PayloadPolicyConfig;
+        use crate::http_server::payload::PayloadPolicy;
+        use crate::http_server::routes::router::{self, RouterConfig, handle_custom};
+
+        use super::*;
+
+        async fn route_with_payload_policy(
+            payload_policy: PayloadPolicyConfig,
+        ) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+            let router_state = RouterState::new(
+                Arc::new(initialize_dependencies!().await),
+                RouterConfig {
+                    payload_policy: PayloadPolicy::from_config(&payload_policy),
+                    ..RouterConfig::dummy()
+                },
+            );
+
+            warp::path!("json-route")
+                .and(warp::post())
+                .and(json_body(&router_state, RouteGroup::Registration))
+                .then(|json: Value| async move { reply::json(&json, StatusCode::OK) })
+                .recover(handle_custom)
+        }
+
+        fn gzip_request(json: &Value) -> warp::test::RequestBuilder {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(json.to_string().as_bytes()).unwrap();
+
+            request()
+                .method(Method::POST.as_str())
+                .path("/json-route")
+                .header("content-encoding", "gzip")
+                .body(encoder.finish().unwrap())
+        }
+
+        #[tokio::test]
+        async fn test_accepts_compressed_body_when_decompression_is_enabled() {
+            let json = serde_json::json!({ "key": "value" });
+            let response = gzip_request(&json)
+                .reply(
+                    &route_with_payload_policy(PayloadPolicyConfig {
+                        request_decompression: true,
+                        ..PayloadPolicyConfig::default()
+                    })
+                    .await,
+                )
+                .await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                json,
+                serde_json::from_slice::<Value>(response.body()).unwrap()
+            );
+        }
+
+        #[tokio::test]
+        async fn test_rejects_compressed_body_when_decompression_is_disabled() {
+            let signer = RegisterSignerMessage::dummy();
+            let routes = router::routes(Arc::new(RouterState::new_with_dummy_config(Arc::new(
+                initialize_dependencies!().await,
+            ))));
+
+            let response = gzip_request(&serde_json::to_value(&signer).unwrap())
+                .path("/aggregator/register-signer")
+                .reply(&routes)
+                .await;
+
+            APISpec::verify_conformity(
+                APISpec::get_default_spec_file_from(crate::http_server::API_SPEC_LOCATION),
+                Method::POST.as_str(),
+                "/register-signer",
+                "application/json",
+                &signer,
+                &response,
+                &StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            )
+            .unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_rejects_too_large_body_on_a_registration_route() {
+            let signatures = RegisterSignatureMessageHttp::dummy();
+            let routes = router::routes(Arc::new(RouterState::new(
+                Arc::new(initialize_dependencies!().await),
+                RouterConfig {
+                    payload_policy: PayloadPolicy::from_config(&PayloadPolicyConfig {
+                        registration_max_body_size_in_bytes: Some(5),
+                        ..PayloadPolicyConfig::default()
+                    }),
+                    ..RouterConfig::dummy()
+                },
+            )));
+
+            let response = request()
+                .method(Method::POST.as_str())
+                .path("/aggregator/register-signatures")
+                .json(&signatures)
+                .reply(&routes)
+                .await;
+
+            APISpec::verify_conformity(
+                APISpec::get_default_spec_file_from(crate::http_server::API_SPEC_LOCATION),
+                Method::POST.as_str(),
+                "/register-signatures",
+                "application/json",
+                &signatures,
+                &response,
+                &StatusCode::PAYLOAD_TOO_LARGE,
+            )
+            .unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_applies_the_max_body_size_of_the_route_group() {
+            let json = serde_json::json!({ "key": "value" });
+            let route = route_with_payload_policy(PayloadPolicyConfig {
+                max_body_size_in_bytes: Some(1000),
+                registration_max_body_size_in_bytes: Some(5),
+                request_decompression: true,
+                ..PayloadPolicyConfig::default()
+            })
+            .await;
+
+            let response = request()
+                .method(Method::POST.as_str())
+                .path("/json-route")
+                .json(&json)
+                .reply(&route)
+                .await;
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+            let response = gzip_request(&json).reply(&route).await;
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        }
+    }
 }
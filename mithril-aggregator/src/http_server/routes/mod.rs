@@ -1,6 +1,7 @@
 mod artifact_routes;
 mod certificate_routes;
 mod epoch_routes;
+mod health_routes;
 mod middlewares;
 mod proof_routes;
 mod protocol_configuration_routes;
@@ -12,9 +13,6 @@ mod signer_routes;
 mod statistics_routes;
 mod status;
 
-/// Shared maximum content length for payloads of 256kb
-const MAX_CONTENT_LENGTH: u64 = 1024 * 256;
-
 /// Match the given result and do an early return with an internal server error (500)
 /// if it was an Error. Else return the unwrapped value.
 #[macro_export]
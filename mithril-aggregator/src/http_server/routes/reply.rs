@@ -41,6 +41,13 @@ pub fn gone(label: String, message: String) -> Box<dyn warp::Reply> {
     json(&ClientError::new(label, message), StatusCode::GONE)
 }
 
+pub fn too_many_requests(label: String, message: String) -> Box<dyn warp::Reply> {
+    json(
+        &ClientError::new(label, message),
+        StatusCode::TOO_MANY_REQUESTS,
+    )
+}
+
 pub fn server_error<E: Into<StdError>>(error: E) -> Box<dyn warp::Reply> {
     let std_error: StdError = error.into();
     let status_code = {
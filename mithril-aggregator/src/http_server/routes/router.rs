@@ -1,8 +1,13 @@
 use crate::ServeCommandDependenciesContainer;
+use crate::configuration::SignatureRegistrationRateLimitConfig;
 use crate::http_server::SERVER_BASE_PATH;
+use crate::http_server::cors::CorsPolicies;
+use crate::http_server::payload::{self, ContentEncoding, InvalidRequestBody, PayloadPolicy};
+use crate::http_server::route_group::{self, RouteGroup};
 use crate::http_server::routes::{
-    artifact_routes, certificate_routes, epoch_routes, protocol_configuration_routes, root_routes,
-    signatures_routes, signer_routes, statistics_routes, status,
+    artifact_routes, certificate_routes, epoch_routes, health_routes,
+    protocol_configuration_routes, root_routes, signatures_routes, signer_routes,
+    statistics_routes, status,
 };
 use crate::tools::url_sanitizer::SanitizedUrlWithTrailingSlash;
 
@@ -10,16 +15,13 @@ use mithril_common::api_version::APIVersionProvider;
 use mithril_common::entities::SignedEntityTypeDiscriminants;
 #[cfg(test)]
 use mithril_common::test::double::Dummy;
-use mithril_common::{
-    AggregateSignatureType, CardanoNetwork, MITHRIL_API_VERSION_HEADER, MITHRIL_CLIENT_TYPE_HEADER,
-    MITHRIL_ORIGIN_TAG_HEADER,
-};
+use mithril_common::{AggregateSignatureType, CardanoNetwork, MITHRIL_API_VERSION_HEADER};
 
 use std::collections::{BTreeSet, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
-use warp::http::Method;
 use warp::http::StatusCode;
+use warp::reject::PayloadTooLarge;
 use warp::{Filter, Rejection, Reply};
 
 use super::{middlewares, proof_routes};
@@ -37,6 +39,9 @@ pub struct RouterConfig {
     pub allow_http_serve_directory: bool,
     pub origin_tag_white_list: HashSet<String>,
     pub aggregate_signature_type: AggregateSignatureType,
+    pub signature_registration_rate_limit: Option<SignatureRegistrationRateLimitConfig>,
+    pub cors_policies: CorsPolicies,
+    pub payload_policy: PayloadPolicy,
 }
 
 #[cfg(test)]
@@ -57,6 +62,9 @@ impl Dummy for RouterConfig {
             allow_http_serve_directory: false,
             origin_tag_white_list: HashSet::from(["DUMMY_TAG".to_string()]),
             aggregate_signature_type: AggregateSignatureType::Concatenation,
+            signature_registration_rate_limit: None,
+            cors_policies: CorsPolicies::default(),
+            payload_policy: PayloadPolicy::default(),
         }
     }
 }
@@ -117,18 +125,7 @@ impl RouterState {
 pub fn routes(
     state: Arc<RouterState>,
 ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
-    let cors = warp::cors()
-        .allow_any_origin()
-        .allow_headers(vec![
-            "content-type",
-            MITHRIL_API_VERSION_HEADER,
-            MITHRIL_ORIGIN_TAG_HEADER,
-            MITHRIL_CLIENT_TYPE_HEADER,
-        ])
-        .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]);
-
-    warp::any()
-        .and(warp::path(SERVER_BASE_PATH))
+    let api_routes = warp::path(SERVER_BASE_PATH)
         .and(
             certificate_routes::routes(&state)
                 .or(artifact_routes::snapshot::routes(&state))
@@ -143,7 +140,8 @@ pub fn routes(
                 .or(protocol_configuration_routes::routes(&state))
                 .or(statistics_routes::routes(&state))
                 .or(root_routes::routes(&state))
-                .or(status::routes(&state)),
+                .or(status::routes(&state))
+                .or(health_routes::routes(&state)),
         )
         .recover(handle_custom)
         .and(middlewares::with_api_version_provider(&state))
@@ -154,13 +152,48 @@ pub fn routes(
                 &api_version_provider.compute_current_version().unwrap().to_string(),
             )
         })
-        .with(cors)
+        // Boxed to keep the type of the filters wrapping it, once per group of routes, shallow
+        .boxed();
+    let cors_policies = &state.configuration.cors_policies;
+    let registration_routes = api_routes.clone().with(cors_policies.get(RouteGroup::Registration));
+    let read_routes = api_routes.with(cors_policies.get(RouteGroup::Read));
+    let response_compression = state.configuration.payload_policy.response_compression();
+
+    // Each request goes through a single branch, so its body is never read twice
+    route_group::with_route_group(RouteGroup::Registration)
+        .and(registration_routes)
+        .map(Reply::into_response)
+        .or(route_group::with_route_group(RouteGroup::Read)
+            .and(payload::with_response_encoding(
+                Some(ContentEncoding::Gzip),
+                response_compression,
+            ))
+            .and(read_routes.clone().with(warp::filters::compression::gzip()))
+            .map(Reply::into_response))
+        .unify()
+        .or(route_group::with_route_group(RouteGroup::Read)
+            .and(payload::with_response_encoding(
+                Some(ContentEncoding::Deflate),
+                response_compression,
+            ))
+            .and(read_routes.clone().with(warp::filters::compression::deflate()))
+            .map(Reply::into_response))
+        .unify()
+        .or(route_group::with_route_group(RouteGroup::Read)
+            .and(payload::with_response_encoding(None, response_compression))
+            .and(read_routes)
+            .map(Reply::into_response))
+        .unify()
         .with(middlewares::log_route_call(&state))
 }
 
-pub async fn handle_custom(reject: Rejection) -> Result<impl Reply, Rejection> {
+pub async fn handle_custom(reject: Rejection) -> Result<Box<dyn Reply>, Rejection> {
     if reject.is_not_found() {
-        Ok(StatusCode::NOT_FOUND)
+        Ok(Box::new(StatusCode::NOT_FOUND))
+    } else if let Some(invalid_request_body) = reject.find::<InvalidRequestBody>() {
+        Ok(invalid_request_body.to_reply())
+    } else if reject.find::<PayloadTooLarge>().is_some() {
+        Ok(InvalidRequestBody::payload_too_large().to_reply())
     } else {
         Err(reject)
     }
@@ -233,4 +266,205 @@ mod tests {
         .status()
         .is_success());
     }
+
+    mod cors_policy {
+        use crate::configuration::CorsPolicyConfig;
+
+        use super::*;
+
+        fn routes_with_cors_policy(
+            container: ServeCommandDependenciesContainer,
+            cors_policy: CorsPolicyConfig,
+        ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+            let state = RouterState::new(
+                Arc::new(container),
+                RouterConfig {
+                    cors_policies: CorsPolicies::from_config(&cors_policy).unwrap(),
+                    ..RouterConfig::dummy()
+                },
+            );
+            routes(Arc::new(state))
+        }
+
+        fn preflight_request(path: &str, origin: &str, method: &str) -> RequestBuilder {
+            warp::test::request()
+                .method("OPTIONS")
+                .path(path)
+                .header("Origin", origin)
+                .header("access-control-request-method", method)
+        }
+
+        #[tokio::test]
+        async fn registration_routes_can_be_locked_down_while_read_routes_stay_public() {
+            let routes = routes_with_cors_policy(
+                initialize_dependencies!().await,
+                CorsPolicyConfig {
+                    registration_allowed_origins: Some(vec![]),
+                    ..CorsPolicyConfig::default()
+                },
+            );
+
+            for path in ["/aggregator/register-signer", "/aggregator/register-signatures"] {
+                let response = preflight_request(path, "http://localhost", "POST")
+                    .reply(&routes)
+                    .await;
+                assert_eq!(
+                    StatusCode::FORBIDDEN,
+                    response.status(),
+                    "Preflight request to '{path}' should be forbidden"
+                );
+            }
+
+            let response = preflight_request("/aggregator/certificates", "http://localhost", "GET")
+                .reply(&routes)
+                .await;
+            assert!(response.status().is_success());
+        }
+
+        #[tokio::test]
+        async fn only_allowed_origins_can_call_the_routes() {
+            let routes = routes_with_cors_policy(
+                initialize_dependencies!().await,
+                CorsPolicyConfig {
+                    allowed_origins: Some(vec!["https://mithril.network".to_string()]),
+                    max_age_in_seconds: Some(600),
+                    ..CorsPolicyConfig::default()
+                },
+            );
+
+            let response = preflight_request("/aggregator", "https://mithril.network", "GET")
+                .reply(&routes)
+                .await;
+            assert!(response.status().is_success());
+            assert_eq!(
+                "https://mithril.network",
+                response.headers().get("access-control-allow-origin").unwrap()
+            );
+            assert_eq!(
+                "600",
+                response.headers().get("access-control-max-age").unwrap()
+            );
+
+            let response = preflight_request("/aggregator", "https://other.network", "GET")
+                .reply(&routes)
+                .await;
+            assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+            let response = preflight_request(
+                "/aggregator/register-signatures",
+                "https://other.network",
+                "POST",
+            )
+            .reply(&routes)
+            .await;
+            assert_eq!(StatusCode::FORBIDDEN, response.status());
+        }
+    }
+
+    mod payload_policy {
+        use mithril_common::temp_dir_create;
+
+        use crate::configuration::PayloadPolicyConfig;
+
+        use super::*;
+
+        async fn routes_with_response_compression(
+            response_compression: bool,
+        ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+            let state = RouterState::new(
+                Arc::new(initialize_dependencies!().await),
+                RouterConfig {
+                    payload_policy: PayloadPolicy::from_config(&PayloadPolicyConfig {
+                        response_compression,
+                        ..PayloadPolicyConfig::default()
+                    }),
+                    ..RouterConfig::dummy()
+                },
+            );
+            routes(Arc::new(state))
+        }
+
+        async fn get_content_encoding(
+            routes: &(impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone + 'static),
+            accept_encoding: Option<&str>,
+        ) -> Option<String> {
+            let request = warp::test::request().path("/aggregator");
+            let request = match accept_encoding {
+                Some(accept_encoding) => request.header("accept-encoding", accept_encoding),
+                None => request,
+            };
+            let response = request.reply(routes).await;
+            assert_eq!(StatusCode::OK, response.status());
+
+            response
+                .headers()
+                .get("content-encoding")
+                .map(|encoding| encoding.to_str().unwrap().to_string())
+        }
+
+        #[tokio::test]
+        async fn responses_are_compressed_with_the_encoding_accepted_by_the_client() {
+            let routes = routes_with_response_compression(true).await;
+
+            assert_eq!(None, get_content_encoding(&routes, None).await);
+            assert_eq!(None, get_content_encoding(&routes, Some("br")).await);
+            assert_eq!(
+                Some("gzip".to_string()),
+                get_content_encoding(&routes, Some("deflate, gzip")).await
+            );
+            assert_eq!(
+                Some("deflate".to_string()),
+                get_content_encoding(&routes, Some("deflate")).await
+            );
+        }
+
+        #[tokio::test]
+        async fn responses_are_not_compressed_when_compression_is_disabled() {
+            let routes = routes_with_response_compression(false).await;
+
+            assert_eq!(None, get_content_encoding(&routes, Some("gzip")).await);
+        }
+
+        #[tokio::test]
+        async fn archives_downloads_are_not_compressed() {
+            let cardano_db_artifacts_directory = temp_dir_create!();
+            let archive_content = vec![7; 4096];
+            std::fs::write(
+                cardano_db_artifacts_directory.join("immutable-00001.tar.zst"),
+                &archive_content,
+            )
+            .unwrap();
+            let state = RouterState::new(
+                Arc::new(initialize_dependencies!().await),
+                RouterConfig {
+                    cardano_db_artifacts_directory,
+                    allow_http_serve_directory: true,
+                    payload_policy: PayloadPolicy::from_config(&PayloadPolicyConfig {
+                        response_compression: true,
+                        ..PayloadPolicyConfig::default()
+                    }),
+                    ..RouterConfig::dummy()
+                },
+            );
+            let routes = routes(Arc::new(state));
+
+            let response = warp::test::request()
+                .path("/aggregator/cardano-database-download/immutable-00001.tar.zst")
+                .header("accept-encoding", "gzip, deflate")
+                .reply(&routes)
+                .await;
+
+            assert_eq!(StatusCode::OK, response.status());
+            assert_eq!(None, response.headers().get("content-encoding"));
+            assert_eq!(
+                Some(&archive_content.len().to_string()),
+                response
+                    .headers()
+                    .get("content-length")
+                    .map(|length| length.to_str().unwrap().to_string())
+                    .as_ref()
+            );
+            assert_eq!(archive_content, response.body().to_vec());
+        }
+    }
 }
@@ -1,7 +1,8 @@
 use warp::Filter;
 
+use crate::http_server::route_group::RouteGroup;
+use crate::http_server::routes::middlewares;
 use crate::http_server::routes::router::RouterState;
-use crate::http_server::routes::{MAX_CONTENT_LENGTH, middlewares};
 
 pub fn routes(
     router_state: &RouterState,
@@ -15,13 +16,17 @@ fn register_signatures(
 ) -> impl Filter<Extract = (impl warp::Reply + use<>,), Error = warp::Rejection> + Clone + use<> {
     warp::path!("register-signatures")
         .and(warp::post())
-        .and(middlewares::json_with_max_length(MAX_CONTENT_LENGTH))
+        .and(middlewares::json_body(
+            router_state,
+            RouteGroup::Registration,
+        ))
         .and(middlewares::with_logger(router_state))
         .and(middlewares::with_certifier_service(router_state))
         .and(middlewares::with_single_signature_authenticator(
             router_state,
         ))
         .and(middlewares::with_metrics_service(router_state))
+        .and(middlewares::validators::with_signature_registration_rate_limiter(router_state))
         .and_then(handlers::register_signatures)
 }
 
@@ -36,6 +41,7 @@ mod handlers {
     use crate::{
         MetricsService, SingleSignatureAuthenticator,
         http_server::routes::reply,
+        http_server::validators::SignatureRegistrationRateLimiter,
         message_adapters::FromRegisterSingleSignatureAdapter,
         services::{CertifierService, CertifierServiceError, SignatureRegistrationStatus},
         unwrap_to_internal_server_error,
@@ -50,6 +56,7 @@ mod handlers {
         certifier_service: Arc<dyn CertifierService>,
         single_signer_authenticator: Arc<SingleSignatureAuthenticator>,
         metrics_service: Arc<MetricsService>,
+        rate_limiter: Arc<SignatureRegistrationRateLimiter>,
     ) -> Result<impl warp::Reply, Infallible> {
         debug!(logger, ">> register_signatures"; "payload" => ?message);
 
@@ -88,6 +95,17 @@ mod handlers {
             ));
         }
 
+        // Checked once authenticated so a party budget can't be consumed by someone else, and
+        // only the registrations accepted by the certifier are counted
+        if let Err(error) = rate_limiter.validate(&single_signature.party_id) {
+            debug!(logger, "register_signatures::rate_limited"; "party_id" => &single_signature.party_id);
+            metrics_service
+                .get_signature_registration_total_rate_limited_since_startup()
+                .increment(&[METRICS_HTTP_ORIGIN]);
+
+            return Ok(reply::too_many_requests(error.label, error.message));
+        }
+
         match certifier_service
             .register_single_signature(&signed_entity_type, &single_signature)
             .await
@@ -114,6 +132,7 @@ mod handlers {
                 }
             },
             Ok(registration_status) => {
+                rate_limiter.record(&single_signature.party_id);
                 metrics_service
                     .get_signature_registration_total_successful_since_startup()
                     .increment(&[METRICS_HTTP_ORIGIN]);
@@ -607,4 +626,153 @@ mod tests {
         )
         .unwrap();
     }
+
+    mod rate_limiting {
+        use crate::ServeCommandDependenciesContainer;
+        use crate::configuration::SignatureRegistrationRateLimitConfig;
+        use crate::http_server::routes::router::RouterConfig;
+
+        use super::*;
+
+        fn router_state_with_rate_limit(
+            dependency_manager: Arc<ServeCommandDependenciesContainer>,
+            max_per_party: u32,
+        ) -> RouterState {
+            RouterState::new(
+                dependency_manager,
+                RouterConfig {
+                    signature_registration_rate_limit: Some(SignatureRegistrationRateLimitConfig {
+                        max_per_party,
+                        window_in_seconds: 3600,
+                    }),
+                    ..RouterConfig::dummy()
+                },
+            )
+        }
+
+        async fn dependency_manager_that_register_everything()
+        -> Arc<ServeCommandDependenciesContainer> {
+            let mut deps = initialize_dependencies!().await;
+            deps.certifier_service = MockBuilder::<MockCertifierService>::configure(|mock| {
+                mock.expect_register_single_signature()
+                    .returning(|_, _| Ok(SignatureRegistrationStatus::Registered));
+            });
+            deps.single_signer_authenticator =
+                Arc::new(SingleSignatureAuthenticator::new_that_authenticate_everything());
+            Arc::new(deps)
+        }
+
+        #[tokio::test]
+        async fn test_register_signatures_return_429_when_party_exceeds_its_rate_limit() {
+            let dependency_manager = dependency_manager_that_register_everything().await;
+            let router = setup_router(router_state_with_rate_limit(dependency_manager, 1));
+            let message = RegisterSignatureMessageHttp::dummy();
+            let method = Method::POST.as_str();
+            let path = "/register-signatures";
+
+            let first_response = request()
+                .method(method)
+                .path(path)
+                .json(&message)
+                .reply(&router)
+                .await;
+            let second_response = request()
+                .method(method)
+                .path(path)
+                .json(&message)
+                .reply(&router)
+                .await;
+
+            assert_eq!(StatusCode::CREATED, first_response.status());
+            APISpec::verify_conformity(
+                APISpec::get_default_spec_file_from(crate::http_server::API_SPEC_LOCATION),
+                method,
+                path,
+                "application/json",
+                &message,
+                &second_response,
+                &StatusCode::TOO_MANY_REQUESTS,
+            )
+            .unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_register_signatures_does_not_count_unauthenticated_signatures_in_party_rate_limit()
+         {
+            let dependency_manager = {
+                let mut deps = initialize_dependencies!().await;
+                deps.single_signer_authenticator =
+                    Arc::new(SingleSignatureAuthenticator::new_that_reject_everything());
+                Arc::new(deps)
+            };
+            let router = setup_router(router_state_with_rate_limit(dependency_manager, 1));
+
+            for _ in 0..3 {
+                let response = request()
+                    .method(Method::POST.as_str())
+                    .path("/register-signatures")
+                    .json(&RegisterSignatureMessageHttp::dummy())
+                    .reply(&router)
+                    .await;
+
+                assert_eq!(StatusCode::BAD_REQUEST, response.status());
+            }
+        }
+
+        #[tokio::test]
+        async fn test_register_signatures_does_not_count_signatures_rejected_by_the_certifier_in_party_rate_limit()
+         {
+            let dependency_manager = {
+                let mut deps = initialize_dependencies!().await;
+                deps.certifier_service = MockBuilder::<MockCertifierService>::configure(|mock| {
+                    mock.expect_register_single_signature().returning(|_, _| {
+                        Err(CertifierServiceError::Expired(SignedEntityType::dummy()).into())
+                    });
+                });
+                deps.single_signer_authenticator =
+                    Arc::new(SingleSignatureAuthenticator::new_that_authenticate_everything());
+                Arc::new(deps)
+            };
+            let router = setup_router(router_state_with_rate_limit(dependency_manager, 1));
+
+            for _ in 0..3 {
+                let response = request()
+                    .method(Method::POST.as_str())
+                    .path("/register-signatures")
+                    .json(&RegisterSignatureMessageHttp::dummy())
+                    .reply(&router)
+                    .await;
+
+                assert_eq!(StatusCode::GONE, response.status());
+            }
+        }
+
+        #[tokio::test]
+        async fn test_register_signatures_increments_signature_registration_total_rate_limited_since_startup_metric_when_rate_limited()
+         {
+            let dependency_manager = dependency_manager_that_register_everything().await;
+            let router = setup_router(router_state_with_rate_limit(dependency_manager.clone(), 1));
+            let initial_rate_limited_counter_value = dependency_manager
+                .metrics_service
+                .get_signature_registration_total_rate_limited_since_startup()
+                .get(&["HTTP"]);
+
+            for _ in 0..2 {
+                request()
+                    .method(Method::POST.as_str())
+                    .path("/register-signatures")
+                    .json(&RegisterSignatureMessageHttp::dummy())
+                    .reply(&router)
+                    .await;
+            }
+
+            assert_eq!(
+                initial_rate_limited_counter_value + 1,
+                dependency_manager
+                    .metrics_service
+                    .get_signature_registration_total_rate_limited_since_startup()
+                    .get(&["HTTP"])
+            );
+        }
+    }
 }
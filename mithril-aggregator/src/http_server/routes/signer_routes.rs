@@ -4,8 +4,9 @@ use warp::Filter;
 use mithril_common::MITHRIL_SIGNER_VERSION_HEADER;
 
 use crate::dependency_injection::EpochServiceWrapper;
+use crate::http_server::route_group::RouteGroup;
+use crate::http_server::routes::middlewares;
 use crate::http_server::routes::router::RouterState;
-use crate::http_server::routes::{MAX_CONTENT_LENGTH, middlewares};
 
 pub fn routes(
     router_state: &RouterState,
@@ -25,7 +26,10 @@ fn register_signer(
         .and(warp::header::optional::<String>(
             MITHRIL_SIGNER_VERSION_HEADER,
         ))
-        .and(middlewares::json_with_max_length(MAX_CONTENT_LENGTH))
+        .and(middlewares::json_body(
+            router_state,
+            RouteGroup::Registration,
+        ))
         .and(middlewares::with_logger(router_state))
         .and(middlewares::with_signer_registerer(router_state))
         .and(middlewares::with_event_transmitter(router_state))
@@ -0,0 +1,392 @@
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server::conn::auto;
+use hyper_util::server::graceful::GracefulShutdown;
+use hyper_util::service::TowerToHyperService;
+use slog::{Logger, debug, warn};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig, crypto::ring};
+use tokio_util::sync::CancellationToken;
+use warp::{Filter, Rejection, Reply};
+
+use mithril_common::StdResult;
+use mithril_common::logging::LoggerExtensions;
+
+/// Maximum duration of the TLS handshake of an incoming connection
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum duration to receive the headers of an HTTP/1 request
+const HEADER_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// TLS configuration of the aggregator HTTP server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerTlsConfiguration {
+    /// Path of the PEM encoded certificate chain presented by the server
+    pub certificate_path: PathBuf,
+
+    /// Path of the PEM encoded private key of the server certificate
+    pub private_key_path: PathBuf,
+
+    /// Path of the PEM encoded certificates of the authorities trusted to issue client
+    /// certificates, if set clients must present a certificate (mutual TLS)
+    pub client_ca_certificate_path: Option<PathBuf>,
+}
+
+impl ServerTlsConfiguration {
+    /// Load the certificates and key files to build the rustls server configuration
+    pub fn build_rustls_config(&self) -> StdResult<Arc<ServerConfig>> {
+        let provider = Arc::new(ring::default_provider());
+        let certificates = Self::load_certificates(&self.certificate_path)?;
+        let private_key =
+            PrivateKeyDer::from_pem_file(&self.private_key_path).with_context(|| {
+                format!(
+                    "Could not read TLS private key from '{}'",
+                    self.private_key_path.display()
+                )
+            })?;
+
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .with_context(|| "Could not select the TLS protocol versions")?;
+        let builder = match &self.client_ca_certificate_path {
+            Some(client_ca_certificate_path) => {
+                let mut root_store = RootCertStore::empty();
+                for certificate in Self::load_certificates(client_ca_certificate_path)? {
+                    root_store.add(certificate).with_context(|| {
+                        format!(
+                            "Invalid TLS client CA certificate in '{}'",
+                            client_ca_certificate_path.display()
+                        )
+                    })?;
+                }
+                let client_verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(root_store), provider)
+                        .build()
+                        .with_context(|| "Could not build the TLS client certificate verifier")?;
+
+                builder.with_client_cert_verifier(client_verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder
+            .with_single_cert(certificates, private_key)
+            .with_context(|| "TLS certificate and private key do not match")?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+        Ok(Arc::new(config))
+    }
+
+    fn load_certificates(path: &Path) -> StdResult<Vec<CertificateDer<'static>>> {
+        let certificates = CertificateDer::pem_file_iter(path)
+            .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+            .with_context(|| {
+                format!("Could not read TLS certificates from '{}'", path.display())
+            })?;
+        if certificates.is_empty() {
+            return Err(anyhow!("No TLS certificate found in '{}'", path.display()));
+        }
+
+        Ok(certificates)
+    }
+}
+
+/// Serve the given routes over TLS until the shutdown signal resolves
+///
+/// Mirror what `warp::serve(..).graceful(..)` does with a plain TCP listener: warp does not
+/// support TLS termination so connections are accepted and handed to hyper here.
+///
+/// The TLS handshakes and the reading of the request headers are time bounded so idle clients
+/// cannot hold connections open, handshakes still in progress are aborted on shutdown.
+pub async fn serve_with_tls<F, R>(
+    routes: F,
+    listener: TcpListener,
+    tls_config: Arc<ServerConfig>,
+    shutdown_signal: impl Future<Output = ()>,
+    logger: Logger,
+) where
+    F: Filter<Extract = (R,), Error = Rejection> + Clone + Send + Sync + 'static,
+    R: Reply,
+{
+    let logger = logger.new_with_name("TlsServer");
+    let tls_acceptor = TlsAcceptor::from(tls_config);
+    let graceful_shutdown = GracefulShutdown::new();
+    let handshakes_cancellation = CancellationToken::new();
+    let mut builder = auto::Builder::new(TokioExecutor::new());
+    builder
+        .http1()
+        .timer(TokioTimer::new())
+        .header_read_timeout(HEADER_READ_TIMEOUT);
+    let mut shutdown_signal = std::pin::pin!(shutdown_signal);
+
+    loop {
+        let (stream, remote_address) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(connection) => connection,
+                Err(error) => {
+                    warn!(logger, "Failed to accept connection"; "error" => ?error);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            },
+            _ = &mut shutdown_signal => break,
+        };
+        let tls_acceptor = tls_acceptor.clone();
+        let handshakes_cancellation = handshakes_cancellation.clone();
+        let builder = builder.clone();
+        let service = TowerToHyperService::new(warp::service(routes.clone()));
+        let watcher = graceful_shutdown.watcher();
+        let logger = logger.clone();
+
+        tokio::spawn(async move {
+            let handshake =
+                tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, tls_acceptor.accept(stream));
+            let stream = match handshakes_cancellation.run_until_cancelled(handshake).await {
+                Some(Ok(Ok(stream))) => stream,
+                Some(Ok(Err(error))) => {
+                    debug!(logger, "TLS handshake failed"; "remote_address" => %remote_address, "error" => ?error);
+                    return;
+                }
+                Some(Err(_)) => {
+                    debug!(logger, "TLS handshake timed out"; "remote_address" => %remote_address);
+                    return;
+                }
+                None => return,
+            };
+            let connection = builder.serve_connection_with_upgrades(TokioIo::new(stream), service);
+            if let Err(error) = watcher.watch(connection).await {
+                debug!(logger, "Connection error"; "remote_address" => %remote_address, "error" => ?error);
+            }
+        });
+    }
+
+    drop(listener);
+    handshakes_cancellation.cancel();
+    graceful_shutdown.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use rcgen::{
+        BasicConstraints, CertificateParams, CertifiedIssuer, ExtendedKeyUsagePurpose, IsCa,
+        KeyPair, KeyUsagePurpose,
+    };
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use tokio::sync::oneshot;
+    use tokio_rustls::TlsConnector;
+    use tokio_rustls::rustls::ClientConfig;
+    use tokio_rustls::rustls::pki_types::ServerName;
+
+    use mithril_common::temp_dir_create;
+
+    use crate::test::TestLogger;
+
+    use super::*;
+
+    struct TestCertificates {
+        configuration: ServerTlsConfiguration,
+        ca_certificate: String,
+        client_certificate: String,
+        client_private_key: String,
+    }
+
+    fn generate_certificates(dir: &Path) -> TestCertificates {
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let ca = CertifiedIssuer::self_signed(ca_params, KeyPair::generate().unwrap()).unwrap();
+
+        let server_key = KeyPair::generate().unwrap();
+        let server_certificate = CertificateParams::new(vec!["localhost".to_string()])
+            .unwrap()
+            .signed_by(&server_key, &ca)
+            .unwrap();
+
+        let client_key = KeyPair::generate().unwrap();
+        let mut client_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client_certificate = client_params.signed_by(&client_key, &ca).unwrap();
+
+        let write = |name: &str, content: String| {
+            let path = dir.join(name);
+            std::fs::write(&path, content).unwrap();
+            path
+        };
+
+        TestCertificates {
+            configuration: ServerTlsConfiguration {
+                certificate_path: write("server.crt", server_certificate.pem()),
+                private_key_path: write("server.key", server_key.serialize_pem()),
+                client_ca_certificate_path: Some(write("ca.crt", ca.pem())),
+            },
+            ca_certificate: ca.pem(),
+            client_certificate: client_certificate.pem(),
+            client_private_key: client_key.serialize_pem(),
+        }
+    }
+
+    async fn start_server(configuration: &ServerTlsConfiguration) -> (u16, oneshot::Sender<()>) {
+        let routes = warp::path("ping").map(|| "pong");
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let tls_config = configuration.build_rustls_config().unwrap();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        tokio::spawn(serve_with_tls(
+            routes,
+            listener,
+            tls_config,
+            async {
+                shutdown_rx.await.ok();
+            },
+            TestLogger::stdout(),
+        ));
+
+        (port, shutdown_tx)
+    }
+
+    /// Send a `GET /ping` request over TLS, trusting only the given CA, and return the raw response
+    async fn get_ping(
+        port: u16,
+        certificates: &TestCertificates,
+        with_client_certificate: bool,
+    ) -> StdResult<String> {
+        let provider = Arc::new(ring::default_provider());
+        let mut root_store = RootCertStore::empty();
+        root_store.add(CertificateDer::from_pem_slice(
+            certificates.ca_certificate.as_bytes(),
+        )?)?;
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(root_store);
+        let client_config = if with_client_certificate {
+            builder.with_client_auth_cert(
+                vec![CertificateDer::from_pem_slice(
+                    certificates.client_certificate.as_bytes(),
+                )?],
+                PrivateKeyDer::from_pem_slice(certificates.client_private_key.as_bytes())?,
+            )?
+        } else {
+            builder.with_no_client_auth()
+        };
+
+        let stream = TcpStream::connect(("127.0.0.1", port)).await?;
+        let mut stream = TlsConnector::from(Arc::new(client_config))
+            .connect(ServerName::try_from("localhost")?, stream)
+            .await?;
+        stream
+            .write_all(b"GET /ping HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+
+        Ok(response)
+    }
+
+    #[test]
+    fn build_rustls_config_fails_if_a_file_is_missing() {
+        let certificates = generate_certificates(&temp_dir_create!());
+
+        ServerTlsConfiguration {
+            private_key_path: PathBuf::from("/non/existing/server.key"),
+            ..certificates.configuration.clone()
+        }
+        .build_rustls_config()
+        .expect_err("Should fail when the private key is missing");
+
+        ServerTlsConfiguration {
+            client_ca_certificate_path: Some(PathBuf::from("/non/existing/ca.crt")),
+            ..certificates.configuration
+        }
+        .build_rustls_config()
+        .expect_err("Should fail when the client CA certificate is missing");
+    }
+
+    #[test]
+    fn build_rustls_config_fails_if_certificate_and_key_do_not_match() {
+        let temp_dir = temp_dir_create!();
+        let certificates = generate_certificates(&temp_dir);
+        let other_dir = temp_dir.join("other");
+        std::fs::create_dir_all(&other_dir).unwrap();
+        let other_certificates = generate_certificates(&other_dir);
+
+        ServerTlsConfiguration {
+            private_key_path: other_certificates.configuration.private_key_path,
+            ..certificates.configuration
+        }
+        .build_rustls_config()
+        .expect_err("Should fail when the key does not match the certificate");
+    }
+
+    #[tokio::test]
+    async fn serve_routes_over_tls() {
+        let certificates = generate_certificates(&temp_dir_create!());
+        let (port, _shutdown_tx) = start_server(&ServerTlsConfiguration {
+            client_ca_certificate_path: None,
+            ..certificates.configuration.clone()
+        })
+        .await;
+
+        let response = get_ping(port, &certificates, false).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.ends_with("pong"), "{response}");
+    }
+
+    #[tokio::test]
+    async fn shutdown_completes_while_a_client_does_not_start_the_tls_handshake() {
+        let certificates = generate_certificates(&temp_dir_create!());
+        let configuration = ServerTlsConfiguration {
+            client_ca_certificate_path: None,
+            ..certificates.configuration.clone()
+        };
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let server = tokio::spawn(serve_with_tls(
+            warp::path("ping").map(|| "pong"),
+            listener,
+            configuration.build_rustls_config().unwrap(),
+            async {
+                shutdown_rx.await.ok();
+            },
+            TestLogger::stdout(),
+        ));
+
+        let mut silent_client = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        // Connections are accepted in order: once this request is served the silent client
+        // connection is accepted and waiting for its handshake
+        get_ping(port, &certificates, false).await.unwrap();
+        shutdown_tx.send(()).unwrap();
+
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("Server should shut down without waiting for the TLS handshake")
+            .unwrap();
+        let mut buffer = [0; 1];
+        assert_eq!(0, silent_client.read(&mut buffer).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn require_a_trusted_client_certificate_when_client_ca_is_set() {
+        let certificates = generate_certificates(&temp_dir_create!());
+        let (port, _shutdown_tx) = start_server(&certificates.configuration).await;
+
+        get_ping(port, &certificates, false)
+            .await
+            .expect_err("Request without client certificate should be rejected");
+
+        let response = get_ping(port, &certificates, true).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+    }
+}
@@ -1,3 +1,5 @@
 mod prover_transactions_hash_validator;
+mod signature_registration_rate_limiter;
 
 pub use prover_transactions_hash_validator::*;
+pub use signature_registration_rate_limiter::*;
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use mithril_common::entities::{ClientError, PartyId};
+
+use crate::configuration::SignatureRegistrationRateLimitConfig;
+
+/// Reject the signature registrations of a party once it exceeds its configured rate
+///
+/// Registrations are counted per party during fixed time windows.
+pub struct SignatureRegistrationRateLimiter {
+    limit: Option<SignatureRegistrationRateLimitConfig>,
+    windows: Mutex<HashMap<PartyId, (Instant, u32)>>,
+}
+
+impl SignatureRegistrationRateLimiter {
+    const LABEL: &'static str = "rate_limited";
+
+    /// Number of tracked parties above which the expired windows are purged
+    const PURGE_THRESHOLD: usize = 10_000;
+
+    pub fn new(limit: Option<SignatureRegistrationRateLimitConfig>) -> Self {
+        Self {
+            limit,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns an error if the party already reached its rate during the current window
+    pub fn validate(&self, party_id: &PartyId) -> Result<(), ClientError> {
+        self.validate_at(party_id, Instant::now())
+    }
+
+    fn validate_at(&self, party_id: &PartyId, now: Instant) -> Result<(), ClientError> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+        let window = Duration::from_secs(limit.window_in_seconds);
+
+        let windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        match windows.get(party_id) {
+            Some((start, count))
+                if now.duration_since(*start) < window && *count >= limit.max_per_party =>
+            {
+                Err(ClientError::new(
+                    Self::LABEL,
+                    format!(
+                        "Too many signature registrations for party '{party_id}', maximum allowed: '{}' every {}s",
+                        limit.max_per_party, limit.window_in_seconds
+                    ),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Count a registration of the given party in its current window
+    pub fn record(&self, party_id: &PartyId) {
+        self.record_at(party_id, Instant::now())
+    }
+
+    fn record_at(&self, party_id: &PartyId, now: Instant) {
+        let Some(limit) = self.limit else {
+            return;
+        };
+        let window = Duration::from_secs(limit.window_in_seconds);
+
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        if windows.len() >= Self::PURGE_THRESHOLD {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < window);
+        }
+
+        let (start, count) = windows.entry(party_id.clone()).or_insert((now, 0));
+        if now.duration_since(*start) >= window {
+            *start = now;
+            *count = 0;
+        }
+        *count = count.saturating_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limiter(max_per_party: u32) -> SignatureRegistrationRateLimiter {
+        SignatureRegistrationRateLimiter::new(Some(SignatureRegistrationRateLimitConfig {
+            max_per_party,
+            window_in_seconds: 10,
+        }))
+    }
+
+    #[test]
+    fn accept_everything_without_limit() {
+        let rate_limiter = SignatureRegistrationRateLimiter::new(None);
+
+        for _ in 0..100 {
+            rate_limiter.validate(&"party".to_string()).unwrap();
+            rate_limiter.record(&"party".to_string());
+        }
+    }
+
+    #[test]
+    fn reject_party_above_its_limit_until_the_window_ends() {
+        let rate_limiter = rate_limiter(2);
+        let party_id = "party".to_string();
+        let start = Instant::now();
+
+        rate_limiter.validate_at(&party_id, start).unwrap();
+        rate_limiter.record_at(&party_id, start);
+        rate_limiter
+            .validate_at(&party_id, start + Duration::from_secs(1))
+            .unwrap();
+        rate_limiter.record_at(&party_id, start + Duration::from_secs(1));
+        let error = rate_limiter
+            .validate_at(&party_id, start + Duration::from_secs(9))
+            .expect_err("Should return an error");
+
+        assert_eq!(
+            error,
+            ClientError::new(
+                "rate_limited",
+                "Too many signature registrations for party 'party', maximum allowed: '2' every 10s"
+            )
+        );
+
+        rate_limiter
+            .validate_at(&party_id, start + Duration::from_secs(10))
+            .unwrap();
+    }
+
+    #[test]
+    fn count_each_party_separately() {
+        let rate_limiter = rate_limiter(1);
+        let now = Instant::now();
+
+        rate_limiter.record_at(&"party-1".to_string(), now);
+        rate_limiter
+            .validate_at(&"party-1".to_string(), now)
+            .expect_err("Should return an error");
+        rate_limiter.validate_at(&"party-2".to_string(), now).unwrap();
+    }
+
+    #[test]
+    fn purge_expired_windows_when_tracking_too_many_parties() {
+        let rate_limiter = rate_limiter(1);
+        let start = Instant::now();
+        for i in 0..SignatureRegistrationRateLimiter::PURGE_THRESHOLD {
+            rate_limiter.record_at(&format!("party-{i}"), start);
+        }
+
+        rate_limiter.record_at(&"new-party".to_string(), start + Duration::from_secs(10));
+
+        assert_eq!(1, rate_limiter.windows.lock().unwrap().len());
+    }
+
+    #[test]
+    fn only_recorded_registrations_count_in_the_party_rate() {
+        let rate_limiter = rate_limiter(1);
+        let party_id = "party".to_string();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            rate_limiter.validate_at(&party_id, now).unwrap();
+        }
+        rate_limiter.record_at(&party_id, now);
+
+        rate_limiter
+            .validate_at(&party_id, now)
+            .expect_err("Should return an error");
+    }
+}
@@ -52,9 +52,13 @@ pub trait MessageService: Sync + Send {
     async fn get_latest_genesis_certificate_message(&self)
     -> StdResult<Option<CertificateMessage>>;
 
-    /// Return the message representation of the last N certificates.
-    async fn get_certificate_list_message(&self, limit: usize)
-    -> StdResult<CertificateListMessage>;
+    /// Return the message representation of at most `limit` certificates, from the latest to
+    /// the oldest, skipping the `offset` latest ones.
+    async fn get_certificate_list_message(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> StdResult<CertificateListMessage>;
 
     /// Return the information regarding the given snapshot.
     async fn get_snapshot_message(
@@ -235,9 +239,12 @@ impl MessageService for MithrilMessageService {
 
     async fn get_certificate_list_message(
         &self,
+        offset: usize,
         limit: usize,
     ) -> StdResult<CertificateListMessage> {
-        self.certificate_repository.get_latest_certificates(limit).await
+        self.certificate_repository
+            .get_latest_certificates_page(offset, limit)
+            .await
     }
 
     async fn get_snapshot_message(
@@ -877,11 +884,29 @@ mod tests {
                 .build()
                 .await;
 
-            let certificate_messages = service.get_certificate_list_message(5).await.unwrap();
+            let certificate_messages = service.get_certificate_list_message(0, 5).await.unwrap();
 
             assert_eq!(2, certificate_messages.len());
             assert_eq!(last_certificate_hash, certificate_messages[0].hash);
         }
+
+        #[tokio::test]
+        async fn get_certificates_page() {
+            let certificates = [
+                fake_data::genesis_certificate("certificate_1"),
+                fake_data::genesis_certificate("certificate_2"),
+                fake_data::genesis_certificate("certificate_3"),
+            ];
+            let service = MessageServiceBuilder::new()
+                .with_certificates(&certificates)
+                .build()
+                .await;
+
+            let certificate_messages = service.get_certificate_list_message(1, 1).await.unwrap();
+
+            assert_eq!(1, certificate_messages.len());
+            assert_eq!(certificates[1].hash, certificate_messages[0].hash);
+        }
     }
 
     mod snapshot {
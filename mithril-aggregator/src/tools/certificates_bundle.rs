@@ -0,0 +1,315 @@
+use anyhow::{Context, anyhow};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use slog::{Logger, info};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+    sync::Arc,
+};
+
+use mithril_common::{
+    StdResult,
+    certificate_chain::{
+        CertificateRetriever, CertificateRetrieverError, CertificateVerifier,
+        MithrilCertificateVerifier,
+    },
+    crypto_helper::ProtocolGenesisVerificationKey,
+    entities::Certificate,
+    logging::LoggerExtensions,
+    messages::CertificateMessage,
+};
+
+use crate::database::repository::CertificateRepository;
+
+/// Certificates exported from an aggregator database
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct CertificatesBundle {
+    /// Version of the aggregator that exported the certificates
+    pub aggregator_version: String,
+
+    /// Certificates, ordered from the latest to the oldest
+    pub certificates: Vec<CertificateMessage>,
+}
+
+/// Tools to export the certificates of an aggregator to a bundle file, and import them back
+/// after checking that they form valid certificate chains.
+pub struct CertificatesBundleTools {
+    certificate_repository: Arc<CertificateRepository>,
+    logger: Logger,
+}
+
+impl CertificatesBundleTools {
+    pub fn new(certificate_repository: Arc<CertificateRepository>, logger: Logger) -> Self {
+        Self {
+            certificate_repository,
+            logger: logger.new_with_component_name::<Self>(),
+        }
+    }
+
+    /// Export all the certificates of the database to the given file, returns the number of
+    /// exported certificates
+    pub async fn export_certificates(&self, target_path: &Path) -> StdResult<usize> {
+        let certificates: Vec<Certificate> = self
+            .certificate_repository
+            .get_latest_certificates(usize::MAX)
+            .await?;
+        let bundle = CertificatesBundle {
+            aggregator_version: env!("CARGO_PKG_VERSION").to_string(),
+            certificates: certificates
+                .into_iter()
+                .map(CertificateMessage::try_from)
+                .collect::<StdResult<_>>()?,
+        };
+
+        let target_file = File::create(target_path).with_context(|| {
+            format!(
+                "Could not create certificates bundle file '{}'",
+                target_path.display()
+            )
+        })?;
+        serde_json::to_writer(BufWriter::new(target_file), &bundle)
+            .with_context(|| "Could not write certificates bundle")?;
+        info!(
+            self.logger, "Exported certificates";
+            "target_path" => target_path.display(), "total_certificates" => bundle.certificates.len()
+        );
+
+        Ok(bundle.certificates.len())
+    }
+
+    /// Import the certificates of the given bundle file in the database, returns the number of
+    /// imported certificates
+    ///
+    /// The database must not contain any certificate: the latest certificate is the one inserted
+    /// last, merging the bundle in an existing chain would change it.
+    ///
+    /// Nothing is imported if any certificate of the bundle can't be verified against its parent,
+    /// or, for genesis certificates, against the given genesis verification key.
+    pub async fn import_certificates(
+        &self,
+        source_path: &Path,
+        genesis_verification_key: &ProtocolGenesisVerificationKey,
+    ) -> StdResult<usize> {
+        let existing_certificates: Vec<Certificate> =
+            self.certificate_repository.get_latest_certificates(1).await?;
+        if !existing_certificates.is_empty() {
+            return Err(anyhow!(
+                "Certificates can only be imported in a database without certificates"
+            ));
+        }
+
+        let source_file = File::open(source_path).with_context(|| {
+            format!(
+                "Could not open certificates bundle file '{}'",
+                source_path.display()
+            )
+        })?;
+        let bundle: CertificatesBundle = serde_json::from_reader(BufReader::new(source_file))
+            .with_context(|| "Could not read certificates bundle")?;
+        let certificates: Vec<Certificate> = bundle
+            .certificates
+            .into_iter()
+            .map(Certificate::try_from)
+            .collect::<StdResult<_>>()?;
+
+        self.verify_certificates(&certificates, genesis_verification_key)
+            .await?;
+
+        let total_certificates = certificates.len();
+        // Parents must be inserted before their children
+        self.certificate_repository
+            .create_many_certificates(certificates.into_iter().rev().collect())
+            .await
+            .with_context(|| "Could not store the imported certificates")?;
+        info!(
+            self.logger, "Imported certificates";
+            "source_path" => source_path.display(), "aggregator_version" => bundle.aggregator_version,
+            "total_certificates" => total_certificates
+        );
+
+        Ok(total_certificates)
+    }
+
+    async fn verify_certificates(
+        &self,
+        certificates: &[Certificate],
+        genesis_verification_key: &ProtocolGenesisVerificationKey,
+    ) -> StdResult<()> {
+        let certificate_verifier = MithrilCertificateVerifier::new(
+            self.logger.clone(),
+            Arc::new(BundleCertificateRetriever::new(certificates)),
+        );
+
+        for certificate in certificates {
+            certificate_verifier
+                .verify_certificate(certificate, genesis_verification_key)
+                .await
+                .with_context(|| format!("Invalid certificate: `{}`", certificate.hash))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Retrieve the parents of the certificates being imported from the bundle itself
+struct BundleCertificateRetriever {
+    certificates: HashMap<String, Certificate>,
+}
+
+impl BundleCertificateRetriever {
+    fn new(certificates: &[Certificate]) -> Self {
+        Self {
+            certificates: certificates
+                .iter()
+                .map(|certificate| (certificate.hash.clone(), certificate.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl CertificateRetriever for BundleCertificateRetriever {
+    async fn get_certificate_details(
+        &self,
+        certificate_hash: &str,
+    ) -> Result<Certificate, CertificateRetrieverError> {
+        self.certificates.get(certificate_hash).cloned().ok_or_else(|| {
+            CertificateRetrieverError(anyhow!(
+                "Certificate `{certificate_hash}` is missing from the bundle"
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mithril_common::crypto_helper::ProtocolGenesisSigner;
+    use mithril_common::temp_dir_create;
+    use mithril_common::test::builder::CertificateChainBuilder;
+
+    use crate::database::test_helper::main_db_connection;
+    use crate::test::TestLogger;
+
+    use super::*;
+
+    fn build_tools() -> (CertificatesBundleTools, Arc<CertificateRepository>) {
+        let certificate_repository = Arc::new(CertificateRepository::new(Arc::new(
+            main_db_connection().unwrap(),
+        )));
+        let tools =
+            CertificatesBundleTools::new(certificate_repository.clone(), TestLogger::stdout());
+
+        (tools, certificate_repository)
+    }
+
+    #[tokio::test]
+    async fn export_then_import_certificates_in_another_database() {
+        let bundle_path = temp_dir_create!().join("certificates.json");
+        let chain = CertificateChainBuilder::new()
+            .with_total_certificates(5)
+            .with_certificates_per_epoch(2)
+            .build();
+        let (source_tools, source_repository) = build_tools();
+        source_repository
+            .create_many_certificates(chain.reversed_chain())
+            .await
+            .unwrap();
+
+        let total_exported = source_tools.export_certificates(&bundle_path).await.unwrap();
+
+        let (target_tools, target_repository) = build_tools();
+        let total_imported = target_tools
+            .import_certificates(&bundle_path, &chain.genesis_verifier.to_verification_key())
+            .await
+            .unwrap();
+
+        assert_eq!(5, total_exported);
+        assert_eq!(5, total_imported);
+        let imported_certificates: Vec<Certificate> =
+            target_repository.get_latest_certificates(usize::MAX).await.unwrap();
+        assert_eq!(chain.certificates_chained, imported_certificates);
+    }
+
+    #[tokio::test]
+    async fn import_nothing_if_a_certificate_parent_is_missing_from_the_bundle() {
+        let bundle_path = temp_dir_create!().join("certificates.json");
+        let chain = CertificateChainBuilder::new().with_total_certificates(3).build();
+        let bundle = CertificatesBundle {
+            aggregator_version: "0.0.1".to_string(),
+            certificates: chain.certificates_chained[..2]
+                .iter()
+                .cloned()
+                .map(|certificate| certificate.try_into().unwrap())
+                .collect(),
+        };
+        serde_json::to_writer(File::create(&bundle_path).unwrap(), &bundle).unwrap();
+        let (tools, repository) = build_tools();
+
+        tools
+            .import_certificates(&bundle_path, &chain.genesis_verifier.to_verification_key())
+            .await
+            .expect_err("Import should fail when a parent certificate is missing");
+
+        let certificates: Vec<Certificate> =
+            repository.get_latest_certificates(usize::MAX).await.unwrap();
+        assert_eq!(Vec::<Certificate>::new(), certificates);
+    }
+
+    #[tokio::test]
+    async fn import_nothing_if_the_genesis_certificate_is_not_signed_with_the_given_key() {
+        let bundle_path = temp_dir_create!().join("certificates.json");
+        let chain = CertificateChainBuilder::new().with_total_certificates(3).build();
+        let other_genesis_verification_key =
+            ProtocolGenesisSigner::create_non_deterministic_signer()
+                .create_verifier()
+                .to_verification_key();
+        let (source_tools, source_repository) = build_tools();
+        source_repository
+            .create_many_certificates(chain.reversed_chain())
+            .await
+            .unwrap();
+        source_tools.export_certificates(&bundle_path).await.unwrap();
+        let (tools, repository) = build_tools();
+
+        tools
+            .import_certificates(&bundle_path, &other_genesis_verification_key)
+            .await
+            .expect_err("Import should fail when the genesis certificate can't be verified");
+
+        let certificates: Vec<Certificate> =
+            repository.get_latest_certificates(usize::MAX).await.unwrap();
+        assert_eq!(Vec::<Certificate>::new(), certificates);
+    }
+
+    #[tokio::test]
+    async fn import_nothing_if_the_database_already_contains_certificates() {
+        let bundle_path = temp_dir_create!().join("certificates.json");
+        let chain = CertificateChainBuilder::new().with_total_certificates(5).build();
+        let bundle = CertificatesBundle {
+            aggregator_version: "0.0.1".to_string(),
+            certificates: chain.certificates_chained[2..]
+                .iter()
+                .cloned()
+                .map(|certificate| certificate.try_into().unwrap())
+                .collect(),
+        };
+        serde_json::to_writer(File::create(&bundle_path).unwrap(), &bundle).unwrap();
+        let (tools, repository) = build_tools();
+        repository
+            .create_many_certificates(chain.reversed_chain())
+            .await
+            .unwrap();
+
+        tools
+            .import_certificates(&bundle_path, &chain.genesis_verifier.to_verification_key())
+            .await
+            .expect_err("Import should fail when the database already contains certificates");
+
+        let certificates: Vec<Certificate> =
+            repository.get_latest_certificates(usize::MAX).await.unwrap();
+        assert_eq!(chain.certificates_chained, certificates);
+    }
+}
@@ -1,3 +1,4 @@
+mod certificates_bundle;
 mod certificates_hash_migrator;
 mod digest_helpers;
 mod era;
@@ -9,6 +10,7 @@ mod single_signature_authenticator;
 pub mod url_sanitizer;
 mod vacuum_tracker;
 
+pub use certificates_bundle::CertificatesBundleTools;
 pub use certificates_hash_migrator::CertificatesHashMigrator;
 pub use digest_helpers::extract_digest_from_path;
 pub use era::EraTools;
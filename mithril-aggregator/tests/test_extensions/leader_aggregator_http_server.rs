@@ -76,7 +76,7 @@ async fn epoch_settings(state: State<LeaderAggregatorRoutesState>) -> Response {
 
 async fn certificates_list(state: State<LeaderAggregatorRoutesState>) -> Response {
     slog::debug!(state.logger, "/certificates");
-    match state.message_service.get_certificate_list_message(5).await {
+    match state.message_service.get_certificate_list_message(0, 5).await {
         Ok(message) => (StatusCode::OK, Json(message)).into_response(),
         Err(err) => internal_server_error(err).into_response(),
     }